@@ -1,7 +1,9 @@
 pub use analyze::*;
+pub use report::*;
 pub use update::*;
 
 mod analyze;
+mod report;
 mod update;
 
 #[cfg(test)]
@@ -0,0 +1,83 @@
+use crate::analyze::{analyze_board, update_board, AnalyzedCell};
+use crate::board::Board;
+use crate::types::StrResult;
+
+/**
+Structural metrics of a board.
+- `clues` - the number of givens.
+- `givens_per_row`, `givens_per_col`, `givens_per_square` - by index, squares by their 1D index.
+- `digit_frequency` - how many times each digit is given, `digit_frequency[v - 1]` for `v`.
+- `placed_by_propagation` - empty cells that `analyze_board` and `update_board` fill in.
+- `candidates` - the options left in the cells that are still empty after that.
+- `bivalue_cells` - the cells that are left with exactly two options.
+- `placed_digits` - the digits that are given in every row.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardReport {
+    pub clues: usize,
+    pub givens_per_row: Vec<usize>,
+    pub givens_per_col: Vec<usize>,
+    pub givens_per_square: Vec<usize>,
+    pub digit_frequency: Vec<usize>,
+    pub placed_by_propagation: usize,
+    pub candidates: usize,
+    pub bivalue_cells: usize,
+    pub placed_digits: Vec<usize>,
+}
+
+fn count_givens(cells: &[usize]) -> usize {
+    cells.iter().filter(|&&v| v != 0).count()
+}
+
+pub fn board_report(board: &Board) -> StrResult<BoardReport> {
+    let size = board.get_size();
+    let mut analyzed_board = analyze_board(board)?;
+    update_board(&mut analyzed_board)?;
+
+    let mut digit_frequency = vec![0; size];
+    for cell in board.filter(|&v| v != 0) {
+        digit_frequency[cell.value - 1] += 1;
+    }
+
+    let mut placed_by_propagation = 0;
+    let mut candidates = 0;
+    let mut bivalue_cells = 0;
+
+    for cell in analyzed_board.get_flat() {
+        match cell.value {
+            AnalyzedCell::Value(_) if *board.at(cell.row, cell.col).unwrap() == 0 => {
+                placed_by_propagation += 1
+            }
+            AnalyzedCell::Value(_) => {}
+            AnalyzedCell::Undetermined(options) => {
+                candidates += options.len();
+
+                if options.len() == 2 {
+                    bivalue_cells += 1;
+                }
+            }
+        }
+    }
+
+    Ok(BoardReport {
+        clues: digit_frequency.iter().sum(),
+        givens_per_row: board
+            .get_rows()
+            .iter()
+            .map(|row| count_givens(row))
+            .collect(),
+        givens_per_col: (0..size)
+            .map(|i| count_givens(board.get_col(i).unwrap()))
+            .collect(),
+        givens_per_square: (0..size)
+            .map(|i| count_givens(board.get_square_1d(i).unwrap()))
+            .collect(),
+        placed_digits: (1..=size)
+            .filter(|&v| digit_frequency[v - 1] == size)
+            .collect(),
+        digit_frequency,
+        placed_by_propagation,
+        candidates,
+        bivalue_cells,
+    })
+}
@@ -1,5 +1,6 @@
-use crate::analyze::{analyze_board, analyze_cell, to_board, AnalyzedCell};
+use crate::analyze::{analyze_board, analyze_cell, board_report, to_board, AnalyzedCell};
 use crate::board::{Board, BoardData};
+use crate::test_utils::{board_from_str, EASY_PUZZLE};
 use crate::types::StrResult;
 
 #[test]
@@ -61,3 +62,37 @@ fn update_board_after_creation() -> StrResult<()> {
     assert_eq!(x.get_value(), Some(4));
     Ok(())
 }
+
+#[test]
+fn should_report_board_structure() -> StrResult<()> {
+    let report = board_report(&board_from_str(EASY_PUZZLE))?;
+
+    assert_eq!(report.clues, 30);
+    assert_eq!(report.givens_per_row, vec![3, 4, 3, 3, 4, 3, 3, 4, 3]);
+    assert_eq!(report.givens_per_col[0], 5);
+    assert_eq!(report.givens_per_square[4], 4);
+    assert_eq!(report.digit_frequency.iter().sum::<usize>(), 30);
+    assert_eq!(report.placed_digits, Vec::<usize>::new());
+    Ok(())
+}
+
+#[test]
+fn should_report_propagation() -> StrResult<()> {
+    let report = board_report(&board_from_str("1234341221430000"))?;
+
+    assert_eq!(report.placed_by_propagation, 4);
+    assert_eq!(report.candidates, 0);
+    assert_eq!(report.digit_frequency, vec![3, 3, 3, 3]);
+
+    // row 2 is left with 3,4 and 1,2, the rows below with three options each
+    let report = board_report(&board_from_str("1234000000000000"))?;
+
+    assert_eq!(report.candidates, 32);
+    assert_eq!(report.bivalue_cells, 4);
+    assert_eq!(report.givens_per_square, vec![2, 2, 0, 0]);
+
+    let report = board_report(&board_from_str("1234341221434321"))?;
+
+    assert_eq!(report.placed_digits, vec![1, 2, 3, 4]);
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use crate::board::Board;
+use crate::types::{PositionalValue, StrResult};
+
+/**
+The changes between two boards of the same size, each one as the cell's
+value before and after it changed.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Patch<T = usize> {
+    size: usize,
+    changes: Vec<(PositionalValue<T>, PositionalValue<T>)>,
+}
+
+impl<T> Patch<T>
+where
+    T: Clone + PartialEq,
+{
+    /**
+    @param changes pairs of the old and the new value of a cell.
+    */
+    pub fn from_changes(
+        size: usize,
+        changes: Vec<(PositionalValue<T>, PositionalValue<T>)>,
+    ) -> StrResult<Self> {
+        for (old, new) in changes.iter() {
+            if (old.row, old.col) != (new.row, new.col) {
+                return Err(format!(
+                    "Change from ({},{}) to ({},{}) is not of a single cell",
+                    old.row, old.col, new.row, new.col
+                ));
+            }
+
+            if old.row >= size || old.col >= size {
+                return Err(format!(
+                    "Change of ({},{}) is out of a board of size {size}",
+                    old.row, old.col
+                ));
+            }
+        }
+
+        Ok(Patch { size, changes })
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn changes(&self) -> &[(PositionalValue<T>, PositionalValue<T>)] {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    // the patch undoing this one
+    pub fn inverse(&self) -> Patch<T> {
+        Patch {
+            size: self.size,
+            changes: self
+                .changes
+                .iter()
+                .map(|(old, new)| (new.clone(), old.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl<T> Board<T>
+where
+    T: Clone + PartialEq,
+{
+    /**
+    @returns the cells whose values differ in `other`, row by row.
+    */
+    pub fn diff(&self, other: &Board<T>) -> StrResult<Patch<T>> {
+        let size = self.get_size();
+
+        if other.get_size() != size {
+            return Err(format!(
+                "Could not diff boards of size {size} and {}",
+                other.get_size()
+            ));
+        }
+
+        let mut changes = Vec::new();
+
+        for row in 0..size {
+            for col in 0..size {
+                let old = self.at(row, col).unwrap();
+                let new = other.at(row, col).unwrap();
+
+                if old != new {
+                    changes.push((
+                        PositionalValue::new(row, col, old.clone()),
+                        PositionalValue::new(row, col, new.clone()),
+                    ));
+                }
+            }
+        }
+
+        Ok(Patch { size, changes })
+    }
+
+    /**
+    Applies the changes of the patch, failing without changing anything when
+    a cell doesn't hold the value the patch changes it from.
+    */
+    pub fn apply_patch(&mut self, patch: &Patch<T>) -> StrResult<()> {
+        if patch.size != self.get_size() {
+            return Err(format!(
+                "Could not apply a patch of size {} to a board of size {}",
+                patch.size,
+                self.get_size()
+            ));
+        }
+
+        for (old, _) in patch.changes.iter() {
+            if self.at(old.row, old.col) != Some(&old.value) {
+                return Err(format!(
+                    "Cell ({},{}) has changed since the patch was made",
+                    old.row, old.col
+                ));
+            }
+        }
+
+        for (_, new) in patch.changes.iter() {
+            self.set(new.row, new.col, new.value.clone())?;
+        }
+
+        Ok(())
+    }
+
+    pub fn revert_patch(&mut self, patch: &Patch<T>) -> StrResult<()> {
+        self.apply_patch(&patch.inverse())
+    }
+}
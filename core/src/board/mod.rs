@@ -1,5 +1,7 @@
 pub use board::*;
+pub use diff::*;
 mod board;
+mod diff;
 
 #[cfg(test)]
 mod test;
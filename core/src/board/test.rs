@@ -149,3 +149,46 @@ fn set_throws_err_in_invalid_col() {
 
     assert_eq!(result.is_err(), true);
 }
+
+#[test]
+fn diff_and_patch_boards() -> StrResult<()> {
+    let before = Board::new(4)?;
+    let mut after = before.clone();
+    after.set(0, 1, 3)?;
+    after.set(2, 2, 4)?;
+
+    let patch = before.diff(&after)?;
+
+    assert_eq!(
+        patch.changes(),
+        &[
+            (PositionalValue::new(0, 1, 0), PositionalValue::new(0, 1, 3)),
+            (PositionalValue::new(2, 2, 0), PositionalValue::new(2, 2, 4)),
+        ]
+    );
+
+    let mut board = before.clone();
+    board.apply_patch(&patch)?;
+    assert_eq!(board.get_rows(), after.get_rows());
+    assert_eq!(board.get_col(1), after.get_col(1));
+
+    board.revert_patch(&patch)?;
+    assert_eq!(board.get_rows(), before.get_rows());
+    assert!(board.diff(&before)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn fail_patch_on_changed_board() -> StrResult<()> {
+    let mut board = Board::new(4)?;
+    let mut target = board.clone();
+    target.set(1, 1, 2)?;
+    let patch = board.diff(&target)?;
+
+    board.set(1, 1, 3)?;
+
+    assert!(board.apply_patch(&patch).is_err());
+    assert_eq!(board.at(1, 1), Some(&3));
+    assert!(board.diff(&Board::new(9)?).is_err());
+    Ok(())
+}
@@ -0,0 +1,40 @@
+// every subset of `items` with exactly `amount` items, keeping their order
+pub(crate) fn combinations(items: &[usize], amount: usize) -> Vec<Vec<usize>> {
+    if amount == 0 {
+        return vec![vec![]];
+    }
+
+    if items.len() < amount {
+        return vec![];
+    }
+
+    let mut with_first: Vec<Vec<usize>> = combinations(&items[1..], amount - 1)
+        .into_iter()
+        .map(|mut rest| {
+            rest.insert(0, items[0]);
+            rest
+        })
+        .collect();
+
+    with_first.extend(combinations(&items[1..], amount));
+    with_first
+}
+
+// every order of `items`
+pub(crate) fn permutations(items: &[usize]) -> Vec<Vec<usize>> {
+    if items.is_empty() {
+        return vec![vec![]];
+    }
+
+    (0..items.len())
+        .flat_map(|i| {
+            let mut rest = items.to_vec();
+            let first = rest.remove(i);
+
+            permutations(&rest).into_iter().map(move |mut permutation| {
+                permutation.insert(0, first);
+                permutation
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,379 @@
+use crate::board::Board;
+use crate::explain::{cell_name, house_name};
+use crate::steps::{House, Technique};
+use crate::types::{PositionalValue, StrResult};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContradictionKind {
+    // the cell has no options left
+    EmptyCell { row: usize, col: usize },
+    // the digit has nowhere to go in the house
+    MissingDigit { house: House, digit: usize },
+    // the digit appears more than once in the house
+    DuplicateDigit { house: House, digit: usize },
+}
+
+/**
+Why `digit` can not be placed in `cell`:
+`by` holds the digit in a peer of `cell`, or it is `cell` itself when the cell is already filled.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Removal {
+    pub cell: (usize, usize),
+    pub digit: usize,
+    pub by: (usize, usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Origin {
+    Given,
+    // `causes` are the placements that removed every other option of the single.
+    Deduced {
+        technique: Technique,
+        house: Option<House>,
+        causes: Vec<(usize, usize)>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placement {
+    pub position: PositionalValue<usize>,
+    pub origin: Origin,
+}
+
+/**
+- `removals` - why each option of the cell (or each cell of the house) is gone.
+- `placements` - every placement the removals depend on, in the order they were made.
+- `givens` - the original givens the contradiction traces back to.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contradiction {
+    pub kind: ContradictionKind,
+    pub removals: Vec<Removal>,
+    pub placements: Vec<Placement>,
+    pub givens: Vec<PositionalValue<usize>>,
+}
+
+enum Progress {
+    Placed,
+    Stuck,
+    Failed(Contradiction),
+}
+
+struct Propagation {
+    board: Board,
+    order: Vec<(usize, usize)>,
+    origins: HashMap<(usize, usize), Origin>,
+}
+
+fn peers_of(row: usize, col: usize, size: usize) -> Vec<(usize, usize)> {
+    [
+        House::Row(row),
+        House::Col(col),
+        House::square_of(row, col, size),
+    ]
+    .iter()
+    .flat_map(|house| house.cells(size))
+    .filter(|&cell| cell != (row, col))
+    .collect()
+}
+
+fn all_houses(size: usize) -> Vec<House> {
+    (0..size)
+        .flat_map(|i| [House::Row(i), House::Col(i), House::Square(i)])
+        .collect()
+}
+
+impl Propagation {
+    fn value_at(&self, (row, col): (usize, usize)) -> usize {
+        *self.board.at(row, col).unwrap()
+    }
+
+    // the first peer holding the digit
+    fn blocker(&self, row: usize, col: usize, digit: usize) -> Option<(usize, usize)> {
+        peers_of(row, col, self.board.get_size())
+            .into_iter()
+            .find(|&cell| self.value_at(cell) == digit)
+    }
+
+    fn options(&self, row: usize, col: usize) -> Vec<usize> {
+        (1..=self.board.get_size())
+            .filter(|&digit| self.blocker(row, col, digit).is_none())
+            .collect()
+    }
+
+    // why the digit can't go in each cell of the house (except `skip`)
+    fn house_removals(
+        &self,
+        house: &House,
+        digit: usize,
+        skip: Option<(usize, usize)>,
+    ) -> Vec<Removal> {
+        house
+            .cells(self.board.get_size())
+            .into_iter()
+            .filter(|&cell| Some(cell) != skip)
+            .filter_map(|cell| {
+                let by = if self.value_at(cell) != 0 {
+                    Some(cell)
+                } else {
+                    self.blocker(cell.0, cell.1, digit)
+                }?;
+                Some(Removal { cell, digit, by })
+            })
+            .collect()
+    }
+
+    fn cell_removals(&self, row: usize, col: usize, skip: Option<usize>) -> Vec<Removal> {
+        (1..=self.board.get_size())
+            .filter(|&digit| Some(digit) != skip)
+            .filter_map(|digit| {
+                let by = self.blocker(row, col, digit)?;
+                Some(Removal {
+                    cell: (row, col),
+                    digit,
+                    by,
+                })
+            })
+            .collect()
+    }
+
+    fn place(&mut self, row: usize, col: usize, value: usize, origin: Origin) -> StrResult<()> {
+        self.board.set(row, col, value)?;
+        self.order.push((row, col));
+        self.origins.insert((row, col), origin);
+        Ok(())
+    }
+
+    fn contradiction(&self, kind: ContradictionKind, removals: Vec<Removal>) -> Contradiction {
+        let mut needed: Vec<(usize, usize)> = Vec::new();
+        let mut stack: Vec<(usize, usize)> = removals.iter().map(|r| r.by).collect();
+
+        while let Some(cell) = stack.pop() {
+            if needed.contains(&cell) {
+                continue;
+            }
+
+            needed.push(cell);
+
+            if let Some(Origin::Deduced { causes, .. }) = self.origins.get(&cell) {
+                stack.extend(causes.iter());
+            }
+        }
+
+        let placements: Vec<Placement> = self
+            .order
+            .iter()
+            .filter(|cell| needed.contains(cell))
+            .map(|&(row, col)| Placement {
+                position: PositionalValue {
+                    row,
+                    col,
+                    value: self.value_at((row, col)),
+                },
+                origin: self.origins[&(row, col)].clone(),
+            })
+            .collect();
+
+        let givens = placements
+            .iter()
+            .filter(|p| p.origin == Origin::Given)
+            .map(|p| p.position.clone())
+            .collect();
+
+        Contradiction {
+            kind,
+            removals,
+            placements,
+            givens,
+        }
+    }
+
+    fn find_duplicate(&self) -> Option<Contradiction> {
+        let size = self.board.get_size();
+
+        for house in all_houses(size) {
+            let cells = house.cells(size);
+
+            for digit in 1..=size {
+                let holders: Vec<(usize, usize)> = cells
+                    .iter()
+                    .copied()
+                    .filter(|&cell| self.value_at(cell) == digit)
+                    .collect();
+
+                if holders.len() > 1 {
+                    let removals = holders
+                        .iter()
+                        .map(|&by| Removal {
+                            cell: by,
+                            digit,
+                            by,
+                        })
+                        .collect();
+                    return Some(self.contradiction(
+                        ContradictionKind::DuplicateDigit { house, digit },
+                        removals,
+                    ));
+                }
+            }
+        }
+
+        None
+    }
+
+    // places a single naked or hidden single, or reports the contradiction that stops it.
+    fn step(&mut self) -> StrResult<Progress> {
+        let size = self.board.get_size();
+
+        for row in 0..size {
+            for col in 0..size {
+                if self.value_at((row, col)) != 0 {
+                    continue;
+                }
+
+                let options = self.options(row, col);
+
+                if options.is_empty() {
+                    let removals = self.cell_removals(row, col, None);
+                    let kind = ContradictionKind::EmptyCell { row, col };
+                    return Ok(Progress::Failed(self.contradiction(kind, removals)));
+                }
+
+                if options.len() == 1 {
+                    let causes = self
+                        .cell_removals(row, col, Some(options[0]))
+                        .into_iter()
+                        .map(|r| r.by)
+                        .collect();
+                    let origin = Origin::Deduced {
+                        technique: Technique::NakedSingle,
+                        house: None,
+                        causes,
+                    };
+                    self.place(row, col, options[0], origin)?;
+                    return Ok(Progress::Placed);
+                }
+            }
+        }
+
+        for house in all_houses(size) {
+            let cells = house.cells(size);
+
+            for digit in 1..=size {
+                if cells.iter().any(|&cell| self.value_at(cell) == digit) {
+                    continue;
+                }
+
+                let spots: Vec<(usize, usize)> = cells
+                    .iter()
+                    .copied()
+                    .filter(|&(row, col)| {
+                        self.value_at((row, col)) == 0 && self.blocker(row, col, digit).is_none()
+                    })
+                    .collect();
+
+                if spots.is_empty() {
+                    let removals = self.house_removals(&house, digit, None);
+                    let kind = ContradictionKind::MissingDigit { house, digit };
+                    return Ok(Progress::Failed(self.contradiction(kind, removals)));
+                }
+
+                if spots.len() == 1 {
+                    let (row, col) = spots[0];
+                    let causes = self
+                        .house_removals(&house, digit, Some(spots[0]))
+                        .into_iter()
+                        .map(|r| r.by)
+                        .collect();
+                    let origin = Origin::Deduced {
+                        technique: Technique::HiddenSingle,
+                        house: Some(house),
+                        causes,
+                    };
+                    self.place(row, col, digit, origin)?;
+                    return Ok(Progress::Placed);
+                }
+            }
+        }
+
+        Ok(Progress::Stuck)
+    }
+}
+
+/**
+Propagates singles from the givens until a cell or a house runs out of options.
+@returns `None` when singles alone don't reach a contradiction.
+*/
+pub fn find_contradiction(board: &Board) -> StrResult<Option<Contradiction>> {
+    let size = board.get_size();
+
+    if let Some(cell) = board.find(|&v| v > size) {
+        return Err(format!(
+            "Value of {} in ({},{}) is not valid: Too big",
+            cell.value, cell.row, cell.col
+        ));
+    }
+
+    let mut propagation = Propagation {
+        board: board.clone(),
+        order: Vec::new(),
+        origins: HashMap::new(),
+    };
+
+    for cell in board.filter(|&v| v != 0) {
+        propagation.order.push((cell.row, cell.col));
+        propagation
+            .origins
+            .insert((cell.row, cell.col), Origin::Given);
+    }
+
+    if let Some(contradiction) = propagation.find_duplicate() {
+        return Ok(Some(contradiction));
+    }
+
+    loop {
+        match propagation.step()? {
+            Progress::Placed => continue,
+            Progress::Stuck => return Ok(None),
+            Progress::Failed(contradiction) => return Ok(Some(contradiction)),
+        }
+    }
+}
+
+impl Display for Contradiction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ContradictionKind::EmptyCell { row, col } => {
+                writeln!(f, "{} has no options left", cell_name(*row, *col))?
+            }
+            ContradictionKind::MissingDigit { house, digit } => {
+                writeln!(f, "{digit} can't go anywhere in {}", house_name(house))?
+            }
+            ContradictionKind::DuplicateDigit { house, digit } => {
+                writeln!(f, "{digit} appears more than once in {}", house_name(house))?
+            }
+        }
+
+        for removal in self.removals.iter() {
+            let (row, col) = removal.cell;
+            let (by_row, by_col) = removal.by;
+
+            if removal.by == removal.cell {
+                writeln!(f, "- {} is taken", cell_name(row, col))?;
+            } else {
+                writeln!(
+                    f,
+                    "- {} can't be {} because of {}",
+                    cell_name(row, col),
+                    removal.digit,
+                    cell_name(by_row, by_col)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
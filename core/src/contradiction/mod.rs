@@ -0,0 +1,6 @@
+pub use contradiction::*;
+
+mod contradiction;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,92 @@
+use crate::board::Board;
+use crate::contradiction::{find_contradiction, ContradictionKind, Origin, Removal};
+use crate::steps::{House, Technique};
+use crate::test_utils::{board_from_str, EASY_PUZZLE};
+use crate::types::StrResult;
+
+#[test]
+fn should_explain_empty_cell() -> StrResult<()> {
+    let mut board = Board::new(4)?;
+    board.set(0, 0, 1)?;
+    board.set(0, 1, 2)?;
+    board.set(0, 2, 3)?;
+    board.set(2, 3, 4)?;
+
+    let contradiction = find_contradiction(&board)?.expect("Contradiction not found");
+
+    assert_eq!(
+        contradiction.kind,
+        ContradictionKind::EmptyCell { row: 0, col: 3 }
+    );
+    assert_eq!(
+        contradiction.removals.last(),
+        Some(&Removal {
+            cell: (0, 3),
+            digit: 4,
+            by: (2, 3)
+        })
+    );
+    assert_eq!(contradiction.givens.len(), 4);
+    Ok(())
+}
+
+#[test]
+fn should_trace_deduced_placements_to_givens() -> StrResult<()> {
+    let mut board = Board::new(4)?;
+    board.set(0, 0, 1)?;
+    board.set(0, 1, 2)?;
+    board.set(0, 2, 3)?;
+    board.set(2, 2, 1)?;
+    board.set(3, 2, 2)?;
+
+    let contradiction = find_contradiction(&board)?.expect("Contradiction not found");
+
+    assert_eq!(
+        contradiction.kind,
+        ContradictionKind::EmptyCell { row: 1, col: 2 }
+    );
+
+    let deduced = contradiction
+        .placements
+        .iter()
+        .find(|p| p.origin != Origin::Given)
+        .expect("Deduced placement not found");
+
+    assert_eq!((deduced.position.row, deduced.position.col), (0, 3));
+    assert_eq!(
+        deduced.origin,
+        Origin::Deduced {
+            technique: Technique::NakedSingle,
+            house: None,
+            causes: vec![(0, 0), (0, 1), (0, 2)],
+        }
+    );
+    assert_eq!(contradiction.givens.len(), 5);
+    Ok(())
+}
+
+#[test]
+fn should_report_duplicates() -> StrResult<()> {
+    let mut board = Board::new(9)?;
+    board.set(0, 0, 1)?;
+    board.set(0, 5, 1)?;
+
+    let contradiction = find_contradiction(&board)?.expect("Contradiction not found");
+
+    assert_eq!(
+        contradiction.kind,
+        ContradictionKind::DuplicateDigit {
+            house: House::Row(0),
+            digit: 1
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn no_contradiction_in_valid_board() -> StrResult<()> {
+    let contradiction = find_contradiction(&board_from_str(EASY_PUZZLE))?;
+
+    assert_eq!(contradiction, None);
+    Ok(())
+}
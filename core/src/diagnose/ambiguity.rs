@@ -0,0 +1,334 @@
+use crate::board::Board;
+use crate::solve::{find_solutions, Grid};
+use crate::types::StrResult;
+
+// boards with up to this many solutions have every pair of them compared
+const SOLUTIONS_LIMIT: usize = 200;
+
+// steps of the search for the fewest differing cells, completing a board takes one for every
+// node of its search
+const NODES_LIMIT: usize = 1_000_000;
+
+/**
+Two solutions of a board that has more than one.
+- `differing` - the cells where the solutions differ, by row and col.
+- `minimal` - false when the search gave up before proving no two solutions differ in fewer cells.
+*/
+#[derive(Debug, Clone)]
+pub struct Ambiguity {
+    pub first: Board,
+    pub second: Board,
+    pub differing: Vec<(usize, usize)>,
+    pub minimal: bool,
+}
+
+fn differing_cells(first: &Board, second: &Board) -> Vec<(usize, usize)> {
+    first
+        .get_flat()
+        .into_iter()
+        .filter(|cell| second.at(cell.row, cell.col) != Some(cell.value))
+        .map(|cell| (cell.row, cell.col))
+        .collect()
+}
+
+/**
+The cells two solutions differ on, with the value of each solution in them.
+Both solutions hold the same digits in every house, so in each house the digits
+of the first one in those cells are the digits of the second one.
+*/
+struct Trade<'a> {
+    board: &'a Board,
+    size: usize,
+    // row, col and square of every cell, as indices into the masks
+    houses: Vec<[usize; 3]>,
+    givens: Vec<u64>,
+    // digits of the first and the second solution in the cells, by house
+    firsts: Vec<u64>,
+    seconds: Vec<u64>,
+    cells: Vec<(usize, usize, usize)>,
+    nodes: usize,
+}
+
+impl<'a> Trade<'a> {
+    fn new(board: &'a Board) -> Trade<'a> {
+        let size = board.get_size();
+        let square_size = board.get_square_size();
+        let houses: Vec<[usize; 3]> = (0..size * size)
+            .map(|index| {
+                let (row, col) = (index / size, index % size);
+                let square = row / square_size * square_size + col / square_size;
+                [row, size + col, 2 * size + square]
+            })
+            .collect();
+        let mut givens = vec![0; 3 * size];
+
+        for cell in board.get_flat() {
+            if *cell.value != 0 {
+                for house in houses[cell.row * size + cell.col] {
+                    givens[house] |= 1 << (cell.value - 1);
+                }
+            }
+        }
+
+        Trade {
+            board,
+            size,
+            houses,
+            givens,
+            firsts: vec![0; 3 * size],
+            seconds: vec![0; 3 * size],
+            cells: Vec::new(),
+            nodes: NODES_LIMIT,
+        }
+    }
+
+    fn is_open(&self, index: usize) -> bool {
+        self.board.at(index / self.size, index % self.size) == Some(&0)
+            && self.cells.iter().all(|&(cell, _, _)| cell != index)
+    }
+
+    // whether the first solution can take `first` in the cell and the second one `second`
+    fn fits(&self, index: usize, first: usize, second: usize) -> bool {
+        let (first_bit, second_bit) = (1 << (first - 1), 1 << (second - 1));
+
+        first != second
+            && self.houses[index].iter().all(|&house| {
+                (self.givens[house] | self.firsts[house]) & first_bit == 0
+                    && (self.givens[house] | self.seconds[house]) & second_bit == 0
+            })
+    }
+
+    fn toggle(&mut self, index: usize, first: usize, second: usize) {
+        for house in self.houses[index] {
+            self.firsts[house] ^= 1 << (first - 1);
+            self.seconds[house] ^= 1 << (second - 1);
+        }
+    }
+
+    fn push(&mut self, index: usize, first: usize, second: usize) {
+        self.toggle(index, first, second);
+        self.cells.push((index, first, second));
+    }
+
+    fn pop(&mut self) {
+        let (index, first, second) = self.cells.pop().unwrap();
+        self.toggle(index, first, second);
+    }
+
+    // every house with a new cell needs one, so at least as many as the most unbalanced rows, cols or squares
+    fn cells_needed(&self) -> usize {
+        (0..3)
+            .map(|kind| {
+                (kind * self.size..(kind + 1) * self.size)
+                    .filter(|&house| self.firsts[house] != self.seconds[house])
+                    .count()
+            })
+            .max()
+            .unwrap()
+    }
+
+    /**
+    The ways to balance the house and digit with the fewest of them, as a cell and the values of both solutions.
+    @returns `None` when every house is balanced.
+    */
+    fn next_cells(&self, after: usize) -> Option<Vec<(usize, usize, usize)>> {
+        let mut best: Option<Vec<(usize, usize, usize)>> = None;
+
+        for house in 0..3 * self.size {
+            let (firsts, seconds) = (self.firsts[house], self.seconds[house]);
+
+            for digit in (1..=self.size).filter(|d| (firsts ^ seconds) & (1 << (d - 1)) != 0) {
+                // the solution missing the digit in this house has to place it in another cell
+                let second_missing = firsts & (1 << (digit - 1)) != 0;
+                let mut options: Vec<(usize, usize, usize)> = Vec::new();
+
+                for index in (after + 1..self.size * self.size)
+                    .filter(|&i| self.houses[i].contains(&house) && self.is_open(i))
+                {
+                    for other in 1..=self.size {
+                        let (first, second) = match second_missing {
+                            true => (other, digit),
+                            false => (digit, other),
+                        };
+
+                        if self.fits(index, first, second) {
+                            options.push((index, first, second));
+                        }
+                    }
+                }
+
+                if best.as_ref().is_none_or(|b| options.len() < b.len()) {
+                    best = Some(options);
+                }
+            }
+        }
+
+        best
+    }
+
+    /**
+    The first solution with the trade's cells taking its values, when the rest of the board can be completed.
+    The second solution then has the same digits in every house, so it completes the same way.
+    */
+    fn completion(&mut self) -> StrResult<Option<Board>> {
+        let mut first = self.board.clone();
+
+        for &(index, value, _) in self.cells.iter() {
+            first.set(index / self.size, index % self.size, value)?;
+        }
+
+        let mut grid = match Grid::from_board(&first)? {
+            Some(grid) => grid,
+            None => return Ok(None),
+        };
+        let mut completed: Option<StrResult<Board>> = None;
+
+        grid.search_within(&mut self.nodes, &mut |solved| {
+            completed = Some(solved.to_board());
+            false
+        });
+
+        completed.transpose()
+    }
+
+    /**
+    Adds up to `budget` more cells after `after` until every house is balanced and the board can be completed.
+    @returns the first solution, with the trade's cells left in place.
+    */
+    fn extend(&mut self, after: usize, budget: usize) -> StrResult<Option<Board>> {
+        if self.nodes == 0 || self.cells_needed() > budget {
+            return Ok(None);
+        }
+        self.nodes -= 1;
+
+        let options = match self.next_cells(after) {
+            None => return self.completion(),
+            Some(options) => options,
+        };
+
+        for (index, first, second) in options {
+            self.push(index, first, second);
+
+            if let Some(solution) = self.extend(after, budget - 1)? {
+                return Ok(Some(solution));
+            }
+
+            self.pop();
+        }
+
+        Ok(None)
+    }
+
+    /**
+    Looks for a trade of exactly `amount` cells, trying every open cell as the one with the lowest index.
+    */
+    fn find(&mut self, amount: usize) -> StrResult<Option<Board>> {
+        let size = self.size;
+        let starts: Vec<(usize, usize, usize)> = (0..size * size)
+            .filter(|&index| self.is_open(index))
+            .flat_map(|index| {
+                (1..=size).flat_map(move |first| (1..=size).map(move |s| (index, first, s)))
+            })
+            .filter(|&(index, first, second)| self.fits(index, first, second))
+            .collect();
+
+        for (index, first, second) in starts {
+            self.push(index, first, second);
+
+            if let Some(solution) = self.extend(index, amount - 1)? {
+                return Ok(Some(solution));
+            }
+
+            self.pop();
+        }
+
+        Ok(None)
+    }
+}
+
+// the two solutions differing in the fewest cells
+fn closest_pair(solutions: &[Board]) -> (Board, Board) {
+    let cells: Vec<Vec<usize>> = solutions
+        .iter()
+        .map(|solution| solution.get_rows_flat().into_iter().copied().collect())
+        .collect();
+    let mut closest = (0, 1, usize::MAX);
+
+    for i in 0..cells.len() {
+        for j in i + 1..cells.len() {
+            let count = cells[i]
+                .iter()
+                .zip(&cells[j])
+                .filter(|(a, b)| a != b)
+                .count();
+
+            if count < closest.2 {
+                closest = (i, j, count);
+            }
+        }
+    }
+
+    (solutions[closest.0].clone(), solutions[closest.1].clone())
+}
+
+/**
+Finds two solutions of the board that differ in as few cells as possible.
+Boards with few solutions have every pair compared. Otherwise sets of differing cells
+are grown one house at a time, smallest first: in every house the differing cells of
+both solutions hold the same digits, and the rest of the board must complete both.
+When that search runs out of steps, the closest pair of the solutions found is given
+with `minimal` set to false.
+@returns `None` when the board has a single solution.
+*/
+pub fn find_ambiguity(board: &Board) -> StrResult<Option<Ambiguity>> {
+    let solutions = find_solutions(board, SOLUTIONS_LIMIT + 1)?;
+
+    match solutions.len() {
+        0 => return Err("Board has no solution".to_string()),
+        1 => return Ok(None),
+        _ => {}
+    }
+
+    let (first, second) = closest_pair(&solutions);
+    let differing = differing_cells(&first, &second);
+
+    if solutions.len() <= SOLUTIONS_LIMIT {
+        return Ok(Some(Ambiguity {
+            first,
+            second,
+            differing,
+            minimal: true,
+        }));
+    }
+
+    let mut trade = Trade::new(board);
+    let size = board.get_size();
+
+    for amount in 1..differing.len() {
+        if let Some(first) = trade.find(amount)? {
+            let mut second = first.clone();
+
+            for &(index, _, value) in trade.cells.iter() {
+                second.set(index / size, index % size, value)?;
+            }
+
+            return Ok(Some(Ambiguity {
+                differing: differing_cells(&first, &second),
+                first,
+                second,
+                minimal: true,
+            }));
+        }
+
+        if trade.nodes == 0 {
+            break;
+        }
+    }
+
+    Ok(Some(Ambiguity {
+        minimal: trade.nodes > 0,
+        first,
+        second,
+        differing,
+    }))
+}
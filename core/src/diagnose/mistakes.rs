@@ -0,0 +1,93 @@
+use crate::board::Board;
+use crate::solve::{count_solutions, find_solutions};
+use crate::types::{PositionalValue, StrResult};
+
+/**
+- `wrong` - entries that don't match the solution, or any of the solutions.
+- `undecidable` - entries that match some of the solutions but not the others.
+- `unique` - whether the givens have a single solution.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct MistakeReport {
+    pub wrong: Vec<PositionalValue<usize>>,
+    pub undecidable: Vec<PositionalValue<usize>>,
+    pub unique: bool,
+}
+
+fn with_value(board: &Board, row: usize, col: usize, value: usize) -> StrResult<Board> {
+    let mut board = board.clone();
+    board.set(row, col, value)?;
+    Ok(board)
+}
+
+/**
+Checks the entries the user added on top of the givens against the solution.
+@param givens the original puzzle.
+@param current the givens together with the user's entries.
+*/
+pub fn find_mistakes(givens: &Board, current: &Board) -> StrResult<MistakeReport> {
+    let size = givens.get_size();
+
+    if current.get_size() != size {
+        return Err(format!(
+            "Board of size {} does not match the givens of size {size}",
+            current.get_size()
+        ));
+    }
+
+    let mut entries: Vec<PositionalValue<usize>> = Vec::new();
+
+    for cell in current.get_flat() {
+        let given = *givens.at(cell.row, cell.col).unwrap();
+        let value = *cell.value;
+
+        if given != 0 && given != value {
+            return Err(format!(
+                "Given {given} in ({},{}) was changed to {value}",
+                cell.row, cell.col
+            ));
+        }
+
+        if given == 0 && value != 0 {
+            entries.push(PositionalValue::new(cell.row, cell.col, value));
+        }
+    }
+
+    let solutions = find_solutions(givens, 2)?;
+
+    let mut report = MistakeReport {
+        wrong: vec![],
+        undecidable: vec![],
+        unique: solutions.len() == 1,
+    };
+
+    match solutions.as_slice() {
+        [] => return Err("The givens have no solution".to_string()),
+        [solution] => {
+            report.wrong = entries
+                .into_iter()
+                .filter(|e| solution.at(e.row, e.col) != Some(&e.value))
+                .collect();
+        }
+        _ => {
+            for entry in entries {
+                let PositionalValue { row, col, value } = entry;
+
+                if count_solutions(&with_value(givens, row, col, value)?, 1)? == 0 {
+                    report.wrong.push(entry);
+                    continue;
+                }
+
+                // an entry every solution agrees on is right even without a unique solution
+                for other in (1..=size).filter(|&v| v != value) {
+                    if count_solutions(&with_value(givens, row, col, other)?, 1)? > 0 {
+                        report.undecidable.push(entry);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,12 @@
+pub use ambiguity::*;
+pub use mistakes::*;
+pub use repair::*;
+pub use unsat::*;
+
+mod ambiguity;
+mod mistakes;
+mod repair;
+mod unsat;
+
+#[cfg(test)]
+mod test;
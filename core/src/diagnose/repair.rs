@@ -0,0 +1,119 @@
+use crate::board::Board;
+use crate::solve::find_solutions;
+use crate::types::{PositionalValue, StrResult};
+
+// every solution is compared with every other one, so boards with more are refused
+const SOLUTIONS_LIMIT: usize = 1_000;
+
+// a set of cells, one bit for each cell index
+type Cells = Vec<u64>;
+
+fn cells_where(size: usize, differs: impl Fn(usize, usize) -> bool) -> Cells {
+    let mut cells = vec![0; (size * size).div_ceil(64)];
+
+    for row in 0..size {
+        for col in 0..size {
+            if differs(row, col) {
+                let index = row * size + col;
+                cells[index / 64] |= 1 << (index % 64);
+            }
+        }
+    }
+
+    cells
+}
+
+fn intersects(a: &Cells, b: &Cells) -> bool {
+    a.iter().zip(b).any(|(x, y)| x & y != 0)
+}
+
+fn count(cells: &Cells) -> u32 {
+    cells.iter().map(|word| word.count_ones()).sum()
+}
+
+/**
+Picks at most `depth` more cells so that every set in `differences` holds one of the picked cells.
+Every set has to be hit, so the smallest one that isn't yet is split on.
+*/
+fn hit_all(differences: &[Cells], picked: &mut Cells, depth: usize) -> bool {
+    let missed = differences
+        .iter()
+        .filter(|difference| !intersects(difference, picked))
+        .min_by_key(|difference| count(difference));
+
+    let missed = match missed {
+        None => return true,
+        Some(_) if depth == 0 => return false,
+        Some(missed) => missed,
+    };
+
+    for (word, &bits) in missed.iter().enumerate() {
+        let mut bits = bits;
+
+        while bits != 0 {
+            let bit = bits & bits.wrapping_neg();
+            bits &= !bit;
+
+            picked[word] |= bit;
+            if hit_all(differences, picked, depth - 1) {
+                return true;
+            }
+            picked[word] &= !bit;
+        }
+    }
+
+    false
+}
+
+/**
+Suggests the fewest givens to add so the board has a single solution.
+The givens make one solution the only one left, so they must hit the cells where it differs
+from each other solution. The smallest such set is searched for every solution, by size.
+@returns an empty list when the solution is already unique,
+or an error when the board has too many solutions to compare.
+*/
+pub fn suggest_givens(board: &Board) -> StrResult<Vec<PositionalValue<usize>>> {
+    let solutions = find_solutions(board, SOLUTIONS_LIMIT + 1)?;
+    let size = board.get_size();
+
+    if solutions.is_empty() {
+        return Err("Board has no solution".to_string());
+    }
+
+    if solutions.len() > SOLUTIONS_LIMIT {
+        return Err(format!(
+            "Board has more than {SOLUTIONS_LIMIT} solutions, add some givens first"
+        ));
+    }
+
+    let differences: Vec<Vec<Cells>> = solutions
+        .iter()
+        .map(|kept| {
+            solutions
+                .iter()
+                .filter(|other| other.get_rows() != kept.get_rows())
+                .map(|other| cells_where(size, |row, col| kept.at(row, col) != other.at(row, col)))
+                .collect()
+        })
+        .collect();
+
+    for depth in 0..=size * size {
+        for (kept, differences) in solutions.iter().zip(differences.iter()) {
+            let mut picked = cells_where(size, |_, _| false);
+
+            if !hit_all(differences, &mut picked, depth) {
+                continue;
+            }
+
+            return Ok((0..size * size)
+                .filter(|index| picked[index / 64] & (1 << (index % 64)) != 0)
+                .map(|index| {
+                    let (row, col) = (index / size, index % size);
+                    PositionalValue::new(row, col, *kept.at(row, col).unwrap())
+                })
+                .collect());
+        }
+    }
+
+    Err("Could not make the solution unique".to_string())
+}
@@ -0,0 +1,215 @@
+use crate::board::Board;
+use crate::diagnose::{
+    find_ambiguity, find_mistakes, minimal_unsatisfiable_givens, suggest_givens,
+};
+use crate::solve::{count_solutions, find_solutions};
+use crate::test_utils::{board_from_str, EASY_PUZZLE, EASY_SOLUTION};
+use crate::types::{PositionalValue, StrResult};
+use crate::validators::is_valid_sudoku;
+
+#[test]
+fn no_unsatisfiable_givens_in_solvable_board() -> StrResult<()> {
+    let board = board_from_str(EASY_PUZZLE);
+
+    assert_eq!(minimal_unsatisfiable_givens(&board)?, None);
+    Ok(())
+}
+
+#[test]
+fn should_find_minimal_unsatisfiable_givens() -> StrResult<()> {
+    // 4 has nowhere to go in col 2: 1 and 2 take (2,2) and (3,2), and the 4 in (0,3)
+    // shares the top right square with (0,2) and (1,2)
+    let mut board = Board::new(4)?;
+    board.set(0, 0, 1)?;
+    board.set(0, 2, 3)?;
+    board.set(0, 3, 4)?;
+    board.set(2, 2, 1)?;
+    board.set(3, 2, 2)?;
+    board.set(3, 0, 3)?;
+
+    let givens = minimal_unsatisfiable_givens(&board)?.expect("Board should be unsolvable");
+
+    assert_eq!(
+        givens,
+        vec![
+            PositionalValue::new(0, 3, 4),
+            PositionalValue::new(2, 2, 1),
+            PositionalValue::new(3, 2, 2),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn duplicates_are_minimal_unsatisfiable() -> StrResult<()> {
+    let mut board = Board::new(9)?;
+    board.set(0, 0, 5)?;
+    board.set(4, 4, 7)?;
+    board.set(8, 0, 5)?;
+
+    let givens = minimal_unsatisfiable_givens(&board)?.expect("Board should be unsolvable");
+
+    assert_eq!(
+        givens,
+        vec![PositionalValue::new(0, 0, 5), PositionalValue::new(8, 0, 5)]
+    );
+    Ok(())
+}
+
+#[test]
+fn should_find_wrong_entries() -> StrResult<()> {
+    let givens = board_from_str(EASY_PUZZLE);
+    let solution = board_from_str(EASY_SOLUTION);
+    let mut current = givens.clone();
+    current.set(0, 2, *solution.at(0, 2).unwrap())?;
+    // 2 is not in row 0 or col 3 yet, but the solution has 6 there
+    current.set(0, 3, 2)?;
+
+    let report = find_mistakes(&givens, &current)?;
+
+    assert!(report.unique);
+    assert_eq!(report.wrong, vec![PositionalValue::new(0, 3, 2)]);
+    assert!(report.undecidable.is_empty());
+    Ok(())
+}
+
+#[test]
+fn entries_are_undecidable_without_unique_solution() -> StrResult<()> {
+    let givens = Board::new(4)?;
+    let mut current = givens.clone();
+    current.set(0, 0, 1)?;
+
+    let report = find_mistakes(&givens, &current)?;
+
+    assert!(!report.unique);
+    assert!(report.wrong.is_empty());
+    assert_eq!(report.undecidable, vec![PositionalValue::new(0, 0, 1)]);
+    Ok(())
+}
+
+#[test]
+fn fail_on_changed_given() -> StrResult<()> {
+    let givens = board_from_str(EASY_PUZZLE);
+    let mut current = givens.clone();
+    current.set(0, 0, 1)?;
+
+    assert!(find_mistakes(&givens, &current).is_err());
+    Ok(())
+}
+
+#[test]
+fn no_suggestions_for_unique_board() -> StrResult<()> {
+    let board = board_from_str(EASY_PUZZLE);
+
+    assert_eq!(suggest_givens(&board)?, vec![]);
+    Ok(())
+}
+
+#[test]
+fn should_suggest_missing_given() -> StrResult<()> {
+    let mut board = board_from_str(EASY_PUZZLE);
+    for (row, col) in [(0, 0), (0, 1), (1, 0), (4, 0)] {
+        board.set(row, col, 0)?;
+    }
+
+    let suggestions = suggest_givens(&board)?;
+
+    for suggestion in suggestions.iter() {
+        assert_eq!(board.at(suggestion.row(), suggestion.col()), Some(&0));
+        board.set(suggestion.row(), suggestion.col(), *suggestion.value())?;
+    }
+
+    assert_eq!(count_solutions(&board, 2)?, 1);
+    // the board has two solutions, one given tells them apart
+    assert_eq!(suggestions.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn should_suggest_givens_for_empty_board() -> StrResult<()> {
+    let mut board = Board::new(4)?;
+    let suggestions = suggest_givens(&board)?;
+
+    for suggestion in suggestions.iter() {
+        board.set(suggestion.row(), suggestion.col(), *suggestion.value())?;
+    }
+
+    assert_eq!(count_solutions(&board, 2)?, 1);
+    // no 4x4 puzzle with fewer than 4 givens has a unique solution
+    assert_eq!(suggestions.len(), 4);
+    Ok(())
+}
+
+#[test]
+fn fail_suggesting_for_too_many_solutions() -> StrResult<()> {
+    assert!(suggest_givens(&Board::new(9)?).is_err());
+    Ok(())
+}
+
+#[test]
+fn no_ambiguity_in_unique_board() -> StrResult<()> {
+    assert!(find_ambiguity(&board_from_str(EASY_PUZZLE))?.is_none());
+    Ok(())
+}
+
+#[test]
+fn should_find_smallest_difference() -> StrResult<()> {
+    let board = Board::new(4)?;
+    let ambiguity = find_ambiguity(&board)?.expect("Ambiguity not found");
+
+    // a 4x4 grid always has some rectangle of 4 cells that can swap
+    assert_eq!(ambiguity.differing.len(), 4);
+    assert!(ambiguity.minimal);
+    assert!(is_valid_sudoku(&ambiguity.first) && is_valid_sudoku(&ambiguity.second));
+    for (row, col) in ambiguity.differing.iter() {
+        assert_ne!(
+            ambiguity.first.at(*row, *col),
+            ambiguity.second.at(*row, *col)
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn should_find_ambiguity_of_missing_givens() -> StrResult<()> {
+    let mut board = board_from_str(EASY_PUZZLE);
+    for (row, col) in [(0, 0), (0, 1), (1, 0), (4, 0)] {
+        board.set(row, col, 0)?;
+    }
+
+    let ambiguity = find_ambiguity(&board)?.expect("Ambiguity not found");
+
+    let solutions = find_solutions(&board, usize::MAX)?;
+    let fewest = solutions
+        .iter()
+        .enumerate()
+        .flat_map(|(i, first)| {
+            solutions[i + 1..]
+                .iter()
+                .map(|second| first.diff(second).unwrap().changes().len())
+        })
+        .min();
+
+    assert!(ambiguity.minimal);
+    assert_eq!(Some(ambiguity.differing.len()), fewest);
+    for (row, col) in ambiguity.differing.iter() {
+        assert_eq!(board.at(*row, *col), Some(&0));
+    }
+    assert_eq!(
+        ambiguity.first.diff(&ambiguity.second)?.changes().len(),
+        ambiguity.differing.len()
+    );
+    Ok(())
+}
+
+#[test]
+fn should_find_smallest_difference_of_empty_9x9() -> StrResult<()> {
+    let ambiguity = find_ambiguity(&Board::new(9)?)?.expect("Ambiguity not found");
+
+    // two rows of a band can swap two digits in two of its squares
+    assert_eq!(ambiguity.differing.len(), 4);
+    assert!(ambiguity.minimal);
+    assert!(is_valid_sudoku(&ambiguity.first) && is_valid_sudoku(&ambiguity.second));
+    assert!(ambiguity.first.is_full() && ambiguity.second.is_full());
+    Ok(())
+}
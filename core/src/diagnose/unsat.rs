@@ -0,0 +1,64 @@
+use crate::board::Board;
+use crate::contradiction::find_contradiction;
+use crate::solve::count_solutions;
+use crate::types::{PositionalValue, StrResult};
+
+fn board_of(size: usize, givens: &[PositionalValue<usize>]) -> StrResult<Board> {
+    let mut board = Board::new(size)?;
+
+    for PositionalValue { row, col, value } in givens.iter() {
+        board.set(*row, *col, *value)?;
+    }
+
+    Ok(board)
+}
+
+fn is_unsolvable(size: usize, givens: &[PositionalValue<usize>]) -> StrResult<bool> {
+    Ok(count_solutions(&board_of(size, givens)?, 1)? == 0)
+}
+
+/**
+Finds a set of givens that is already unsolvable on its own, where removing any
+one of them makes it solvable again.
+@returns `None` when the board has a solution.
+*/
+pub fn minimal_unsatisfiable_givens(
+    board: &Board,
+) -> StrResult<Option<Vec<PositionalValue<usize>>>> {
+    let size = board.get_size();
+
+    if count_solutions(board, 1)? > 0 {
+        return Ok(None);
+    }
+
+    // the givens behind a singles contradiction are usually a much smaller start
+    let mut givens: Vec<PositionalValue<usize>> = match find_contradiction(board)? {
+        Some(contradiction) => contradiction.givens,
+        None => board
+            .filter(|&v| v != 0)
+            .into_iter()
+            .map(|cell| PositionalValue {
+                row: cell.row,
+                col: cell.col,
+                value: *cell.value,
+            })
+            .collect(),
+    };
+
+    let mut index = 0;
+
+    while index < givens.len() {
+        let mut without = givens.clone();
+        without.remove(index);
+
+        if is_unsolvable(size, &without)? {
+            givens = without;
+        } else {
+            index += 1;
+        }
+    }
+
+    givens.sort_by_key(|given| (given.row, given.col));
+
+    Ok(Some(givens))
+}
@@ -0,0 +1,217 @@
+use crate::steps::{House, SolveStep, Technique};
+use crate::types::{PositionalValue, StrResult};
+use std::collections::BTreeMap;
+
+/**
+A language independent form of an explanation, meant to be localized by the client.
+- `key` - identifies the sentence, e.g. `hidden_single`, or `hidden_single_unknown_house`
+  for a hidden single without a `base`, as parsed from HoDoKu's notation.
+- `params` - the values to fill in, using the short notation (`r4c6`, `b5`, `r1c8<>3`).
+  lists are separated by commas.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepMessage {
+    pub key: &'static str,
+    pub params: BTreeMap<&'static str, String>,
+}
+
+pub fn cell_name(row: usize, col: usize) -> String {
+    format!("r{}c{}", row + 1, col + 1)
+}
+
+pub fn house_name(house: &House) -> String {
+    match house {
+        House::Row(i) => format!("row {}", i + 1),
+        House::Col(i) => format!("column {}", i + 1),
+        House::Square(i) => format!("box {}", i + 1),
+    }
+}
+
+pub(crate) fn house_short_name(house: &House) -> String {
+    match house {
+        House::Row(i) => format!("r{}", i + 1),
+        House::Col(i) => format!("c{}", i + 1),
+        House::Square(i) => format!("b{}", i + 1),
+    }
+}
+
+// "a", "a and b", "a, b and c"
+fn join_english(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [single] => single.clone(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
+    }
+}
+
+fn houses_name(houses: &[House]) -> String {
+    let numbers: Vec<String> = houses
+        .iter()
+        .map(|house| match house {
+            House::Row(i) | House::Col(i) | House::Square(i) => (i + 1).to_string(),
+        })
+        .collect();
+
+    let kind = match houses.first() {
+        Some(House::Row(_)) => "row",
+        Some(House::Col(_)) => "column",
+        Some(House::Square(_)) => "box",
+        None => return String::new(),
+    };
+
+    if houses.len() == 1 {
+        format!("{kind} {}", numbers[0])
+    } else {
+        format!("{kind}s {}", join_english(&numbers))
+    }
+}
+
+pub(crate) fn cells_names(cells: &[(usize, usize)]) -> Vec<String> {
+    cells
+        .iter()
+        .map(|&(row, col)| cell_name(row, col))
+        .collect()
+}
+
+pub(crate) fn digits_names(digits: &[usize]) -> Vec<String> {
+    digits.iter().map(|d| d.to_string()).collect()
+}
+
+// "3 is removed from r1c8 and r1c9; 7 is removed from r1c9"
+fn eliminations_english(eliminations: &[PositionalValue<usize>]) -> String {
+    let mut digits: Vec<usize> = Vec::new();
+
+    for elimination in eliminations.iter() {
+        if !digits.contains(&elimination.value) {
+            digits.push(elimination.value);
+        }
+    }
+
+    digits
+        .iter()
+        .map(|&digit| {
+            let cells: Vec<String> = eliminations
+                .iter()
+                .filter(|e| e.value == digit)
+                .map(|e| cell_name(e.row, e.col))
+                .collect();
+
+            format!("{digit} is removed from {}", join_english(&cells))
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/**
+Describes the step as a plain english sentence, e.g. "In box 5, 7 can only go in r4c6".
+@returns an error when the step lacks the digit or the cell its technique needs.
+*/
+pub fn explain_step(step: &SolveStep) -> StrResult<String> {
+    let cells = cells_names(&step.cells);
+    let digits = digits_names(&step.digits);
+    // steps parsed from notation don't always say which house they are about
+    let base = match (step.base.is_empty(), step.cells.len()) {
+        (false, _) => houses_name(&step.base),
+        (true, 1) => "one of its houses".to_string(),
+        (true, _) => "a house they share".to_string(),
+    };
+    let cover = houses_name(&step.cover);
+    let eliminations = eliminations_english(&step.eliminations);
+
+    let digit = digits
+        .first()
+        .ok_or(format!("{} step has no digits", step.technique.name()));
+    let cell = cells
+        .first()
+        .ok_or(format!("{} step has no cells", step.technique.name()));
+
+    Ok(match step.technique {
+        Technique::NakedSingle => {
+            format!("{} is the only candidate left in {}", digit?, cell?)
+        }
+        Technique::HiddenSingle => {
+            format!("In {base}, {} can only go in {}", digit?, cell?)
+        }
+        Technique::LockedCandidatesPointing | Technique::LockedCandidatesClaiming => {
+            format!(
+                "In {base}, {} can only go in {cover}, so {eliminations}",
+                digit?
+            )
+        }
+        Technique::NakedPair | Technique::NakedTriple => format!(
+            "In {base}, {} can only be {}, so {eliminations}",
+            join_english(&cells),
+            digits.join(" or ")
+        ),
+        Technique::HiddenPair | Technique::HiddenTriple => format!(
+            "In {base}, {} can only go in {}, so {eliminations}",
+            join_english(&digits),
+            join_english(&cells)
+        ),
+        Technique::XWing | Technique::Swordfish => format!(
+            "In {base}, {} can only go in {cover}, so {eliminations}",
+            digit?
+        ),
+    })
+}
+
+pub fn explain_steps(steps: &[SolveStep]) -> StrResult<Vec<String>> {
+    steps.iter().map(explain_step).collect()
+}
+
+/**
+Describes the step as a key and parameters, see `StepMessage`.
+*/
+pub fn step_message(step: &SolveStep) -> StepMessage {
+    let key = match step.technique {
+        Technique::NakedSingle => "naked_single",
+        Technique::HiddenSingle if step.base.is_empty() => "hidden_single_unknown_house",
+        Technique::HiddenSingle => "hidden_single",
+        Technique::LockedCandidatesPointing => "locked_candidates_pointing",
+        Technique::LockedCandidatesClaiming => "locked_candidates_claiming",
+        Technique::NakedPair | Technique::NakedTriple => "naked_subset",
+        Technique::HiddenPair | Technique::HiddenTriple => "hidden_subset",
+        Technique::XWing | Technique::Swordfish => "fish",
+    };
+
+    let mut params: BTreeMap<&'static str, String> = BTreeMap::new();
+    let join_houses = |houses: &[House]| {
+        houses
+            .iter()
+            .map(house_short_name)
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+
+    params.insert("technique", step.technique.name().to_string());
+    params.insert("cells", cells_names(&step.cells).join(","));
+    params.insert("digits", digits_names(&step.digits).join(","));
+
+    if !step.base.is_empty() {
+        params.insert("base", join_houses(&step.base));
+    }
+
+    if !step.cover.is_empty() {
+        params.insert("cover", join_houses(&step.cover));
+    }
+
+    if !step.placements.is_empty() {
+        let placements: Vec<String> = step
+            .placements
+            .iter()
+            .map(|p| format!("{}={}", cell_name(p.row, p.col), p.value))
+            .collect();
+        params.insert("placements", placements.join(","));
+    }
+
+    if !step.eliminations.is_empty() {
+        let eliminations: Vec<String> = step
+            .eliminations
+            .iter()
+            .map(|e| format!("{}<>{}", cell_name(e.row, e.col), e.value))
+            .collect();
+        params.insert("eliminations", eliminations.join(","));
+    }
+
+    StepMessage { key, params }
+}
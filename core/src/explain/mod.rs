@@ -0,0 +1,6 @@
+pub use explain::*;
+
+mod explain;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,131 @@
+use crate::explain::{explain_step, explain_steps, step_message};
+use crate::steps::{format_step, parse_step, trace_solve, House, SolveStep, Technique};
+use crate::test_utils::{board_from_str, EASY_PUZZLE};
+use crate::types::{PositionalValue, StrResult};
+
+fn hidden_single() -> SolveStep {
+    SolveStep {
+        technique: Technique::HiddenSingle,
+        base: vec![House::Square(4)],
+        cover: vec![],
+        cells: vec![(3, 5)],
+        digits: vec![7],
+        placements: vec![PositionalValue {
+            row: 3,
+            col: 5,
+            value: 7,
+        }],
+        eliminations: vec![],
+    }
+}
+
+#[test]
+fn should_explain_hidden_single() -> StrResult<()> {
+    assert_eq!(
+        explain_step(&hidden_single())?,
+        "In box 5, 7 can only go in r4c6"
+    );
+    Ok(())
+}
+
+#[test]
+fn fail_on_incomplete_step() {
+    let mut step = hidden_single();
+    step.digits.clear();
+
+    assert!(explain_step(&step).is_err());
+
+    step = hidden_single();
+    step.cells.clear();
+
+    assert!(explain_step(&step).is_err());
+}
+
+#[test]
+fn should_explain_fish() -> StrResult<()> {
+    let step = SolveStep {
+        technique: Technique::XWing,
+        base: vec![House::Row(1), House::Row(5)],
+        cover: vec![House::Col(2), House::Col(6)],
+        cells: vec![(1, 2), (1, 6), (5, 2), (5, 6)],
+        digits: vec![4],
+        placements: vec![],
+        eliminations: vec![
+            PositionalValue {
+                row: 0,
+                col: 2,
+                value: 4,
+            },
+            PositionalValue {
+                row: 7,
+                col: 6,
+                value: 4,
+            },
+        ],
+    };
+
+    assert_eq!(
+        explain_step(&step)?,
+        "In rows 2 and 6, 4 can only go in columns 3 and 7, so 4 is removed from r1c3 and r8c7"
+    );
+    Ok(())
+}
+
+#[test]
+fn should_build_step_message() {
+    let message = step_message(&hidden_single());
+
+    assert_eq!(message.key, "hidden_single");
+    assert_eq!(message.params.get("base"), Some(&"b5".to_string()));
+    assert_eq!(
+        message.params.get("placements"),
+        Some(&"r4c6=7".to_string())
+    );
+    assert_eq!(message.params.get("eliminations"), None);
+}
+
+#[test]
+fn should_explain_every_traced_step() -> StrResult<()> {
+    let trace = trace_solve(&board_from_str(EASY_PUZZLE))?;
+
+    let explanations = explain_steps(&trace.steps)?;
+
+    assert_eq!(explanations.len(), trace.steps.len());
+    assert!(explanations.iter().all(|text| !text.is_empty()));
+    Ok(())
+}
+
+#[test]
+fn should_explain_hidden_single_without_house() -> StrResult<()> {
+    let mut step = hidden_single();
+    step.base.clear();
+
+    assert_eq!(
+        explain_step(&step)?,
+        "In one of its houses, 7 can only go in r4c6"
+    );
+    assert_eq!(step_message(&step).key, "hidden_single_unknown_house");
+    Ok(())
+}
+
+#[test]
+fn should_explain_steps_parsed_from_notation() -> StrResult<()> {
+    let trace = trace_solve(&board_from_str(
+        "000000007507000600016000950060100000000962004005000300000206080000080700320700000",
+    ))?;
+
+    assert!(trace
+        .steps
+        .iter()
+        .any(|step| step.technique == Technique::HiddenSingle));
+
+    for step in trace.steps.iter() {
+        let parsed = parse_step(&format_step(step, 9), 9)?;
+        let explanation = explain_step(&parsed)?;
+
+        assert_eq!(parsed.placements, step.placements);
+        assert_eq!(parsed.eliminations, step.eliminations);
+        assert!(!explanation.contains("In ,"), "{explanation}");
+    }
+    Ok(())
+}
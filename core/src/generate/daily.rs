@@ -0,0 +1,29 @@
+use crate::generate::{daily_v1, Puzzle};
+use crate::steps::Difficulty;
+use crate::types::StrResult;
+
+/**
+The first daily puzzle algorithm. Every puzzle it made must stay the same,
+changes to the generation go into a new algorithm id.
+*/
+pub const DAILY_V1: &str = "daily-v1";
+
+pub const LATEST_DAILY_ALGORITHM: &str = DAILY_V1;
+
+/**
+Maps any key (such as a date) to a 9x9 puzzle of the given difficulty,
+the same one on every platform and crate version for the same `algorithm`.
+The puzzle is always of the asked difficulty, an error is returned when none comes up
+within a fixed number of tries.
+Expert puzzles are rare: in a native release build they took about 1s on average and
+up to about 5s over 40 keys, and running out of tries would take about 30s.
+Wasm takes a few times that, so they are best made in a worker.
+The other difficulties take well under a second.
+@param algorithm the id of the algorithm, such as `DAILY_V1`.
+*/
+pub fn daily_puzzle(algorithm: &str, key: &str, difficulty: Difficulty) -> StrResult<Puzzle> {
+    match algorithm {
+        DAILY_V1 => daily_v1::generate(key, difficulty),
+        _ => Err(format!("Unknown daily puzzle algorithm: {algorithm}")),
+    }
+}
@@ -0,0 +1,664 @@
+// Everything the `daily-v1` puzzles depend on, kept apart from the rest of the crate.
+// Nothing here may change, not even to fix a bug, as every puzzle it made would change with it.
+// That includes the technique finders scoring the puzzles, copied here from `steps` rather than shared.
+// The only shared code deciding a puzzle is `has_unique_solution`, whose answer can't change.
+// `rate` only fills in the returned rating, and `Technique` and `Difficulty` are only used as names.
+
+use crate::board::Board;
+use crate::generate::{Puzzle, DAILY_V1};
+use crate::solve::has_unique_solution;
+use crate::steps::{rate, Difficulty, Technique};
+use crate::types::StrResult;
+
+const SIZE: usize = 9;
+
+// expert puzzles take about 180 grids on average and took up to 830 over 40 keys,
+// the other difficulties under 50
+const ATTEMPTS: usize = 5_000;
+
+const SQUARE_SIZE: usize = 3;
+
+const FULL_MASK: u16 = (1 << SIZE) - 1;
+
+const TECHNIQUES: [Technique; 10] = [
+    Technique::NakedSingle,
+    Technique::HiddenSingle,
+    Technique::LockedCandidatesPointing,
+    Technique::LockedCandidatesClaiming,
+    Technique::NakedPair,
+    Technique::HiddenPair,
+    Technique::NakedTriple,
+    Technique::HiddenTriple,
+    Technique::XWing,
+    Technique::Swordfish,
+];
+
+const UNSOLVED_SCORE: u32 = 1000;
+
+// FNV-1a, so the seed of a key never depends on the platform or std's hasher
+fn hash_key(key: &str) -> u64 {
+    key.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// SplitMix64, kept here rather than taken from rand so its output can never change
+struct DailyRng(u64);
+
+impl DailyRng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // a value in 0..bound, rejecting the values that would bias the modulo
+    fn below(&mut self, bound: usize) -> usize {
+        let bound = bound as u64;
+        let zone = u64::MAX - u64::MAX % bound;
+
+        loop {
+            let value = self.next();
+
+            if value < zone {
+                return (value % bound) as usize;
+            }
+        }
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+fn difficulty_id(difficulty: Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Easy => "easy",
+        Difficulty::Medium => "medium",
+        Difficulty::Hard => "hard",
+        Difficulty::Expert => "expert",
+        Difficulty::Extreme => "extreme",
+    }
+}
+
+fn technique_score(technique: Technique) -> u32 {
+    match technique {
+        Technique::NakedSingle => 1,
+        Technique::HiddenSingle => 5,
+        Technique::LockedCandidatesPointing => 20,
+        Technique::LockedCandidatesClaiming => 22,
+        Technique::NakedPair => 40,
+        Technique::HiddenPair => 45,
+        Technique::NakedTriple => 55,
+        Technique::HiddenTriple => 60,
+        Technique::XWing => 80,
+        Technique::Swordfish => 90,
+    }
+}
+
+fn score_range(difficulty: Difficulty) -> (u32, u32) {
+    match difficulty {
+        Difficulty::Easy => (0, 19),
+        Difficulty::Medium => (20, 39),
+        Difficulty::Hard => (40, 79),
+        Difficulty::Expert => (80, UNSOLVED_SCORE - 1),
+        Difficulty::Extreme => (UNSOLVED_SCORE, u32::MAX),
+    }
+}
+
+// every way to pick `amount` of `SIZE` items, as bit masks
+fn subsets(amount: u32) -> Vec<u16> {
+    (0..1 << SIZE)
+        .filter(|mask: &u16| mask.count_ones() == amount)
+        .collect()
+}
+
+/**
+The candidates of a 9x9 puzzle, for finding which techniques it needs.
+Houses are the rows, then the cols, then the squares.
+*/
+struct Candidates {
+    cells: [usize; SIZE * SIZE],
+    options: [u16; SIZE * SIZE],
+    houses: Vec<[usize; SIZE]>,
+    pairs: Vec<u16>,
+    triples: Vec<u16>,
+}
+
+impl Candidates {
+    fn new(puzzle: &Board) -> Candidates {
+        let mut houses: Vec<[usize; SIZE]> = Vec::new();
+        houses.extend((0..SIZE).map(|i| std::array::from_fn(|col| i * SIZE + col)));
+        houses.extend((0..SIZE).map(|i| std::array::from_fn(|row| row * SIZE + i)));
+        houses.extend((0..SIZE).map(|i| {
+            let (start_row, start_col) =
+                (i / SQUARE_SIZE * SQUARE_SIZE, i % SQUARE_SIZE * SQUARE_SIZE);
+            std::array::from_fn(|j| {
+                (start_row + j / SQUARE_SIZE) * SIZE + start_col + j % SQUARE_SIZE
+            })
+        }));
+
+        let mut candidates = Candidates {
+            cells: [0; SIZE * SIZE],
+            options: [FULL_MASK; SIZE * SIZE],
+            houses,
+            pairs: subsets(2),
+            triples: subsets(3),
+        };
+
+        for cell in puzzle.get_flat() {
+            if *cell.value != 0 {
+                candidates.place(cell.row * SIZE + cell.col, *cell.value);
+            }
+        }
+
+        candidates
+    }
+
+    fn place(&mut self, index: usize, value: usize) {
+        let (row, col, square) = (index / SIZE, index % SIZE, FillGrid::square(index));
+        self.cells[index] = value;
+        self.options[index] = 0;
+
+        for peer in (0..SIZE * SIZE)
+            .filter(|&i| i / SIZE == row || i % SIZE == col || FillGrid::square(i) == square)
+        {
+            self.options[peer] &= !(1 << (value - 1));
+        }
+    }
+
+    // removes `digits` from the cell, telling whether any of them was there
+    fn eliminate(&mut self, index: usize, digits: u16) -> bool {
+        let removed = self.options[index] & digits != 0;
+        self.options[index] &= !digits;
+        removed
+    }
+
+    // positions in the house of the cells that may hold the digit
+    fn positions(&self, house: usize, digit: usize) -> u16 {
+        (0..SIZE)
+            .filter(|&j| self.options[self.houses[house][j]] & (1 << (digit - 1)) != 0)
+            .fold(0, |mask, j| mask | 1 << j)
+    }
+
+    fn subsets(&self, amount: u32) -> Vec<u16> {
+        match amount {
+            2 => self.pairs.clone(),
+            _ => self.triples.clone(),
+        }
+    }
+
+    fn naked_single(&mut self) -> bool {
+        match (0..SIZE * SIZE).find(|&i| self.options[i].count_ones() == 1) {
+            Some(index) => {
+                self.place(index, self.options[index].trailing_zeros() as usize + 1);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn hidden_single(&mut self) -> bool {
+        for house in 0..self.houses.len() {
+            for digit in 1..=SIZE {
+                let positions = self.positions(house, digit);
+
+                if positions.count_ones() == 1 {
+                    let index = self.houses[house][positions.trailing_zeros() as usize];
+                    self.place(index, digit);
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    // the digit's cells of a square sharing a line, or of a line sharing a square
+    fn locked_candidates(&mut self, pointing: bool) -> bool {
+        let bases = match pointing {
+            true => 2 * SIZE..3 * SIZE,
+            false => 0..2 * SIZE,
+        };
+
+        for base in bases {
+            for digit in 1..=SIZE {
+                let positions = self.positions(base, digit);
+
+                if positions.count_ones() < 2 {
+                    continue;
+                }
+
+                let cells: Vec<usize> = (0..SIZE)
+                    .filter(|&j| positions & (1 << j) != 0)
+                    .map(|j| self.houses[base][j])
+                    .collect();
+                let covers = match pointing {
+                    true => vec![cells[0] / SIZE, SIZE + cells[0] % SIZE],
+                    false => vec![2 * SIZE + FillGrid::square(cells[0])],
+                };
+
+                for cover in covers {
+                    if !cells.iter().all(|cell| self.houses[cover].contains(cell)) {
+                        continue;
+                    }
+
+                    let mut changed = false;
+                    for index in self.houses[cover] {
+                        if !cells.contains(&index) {
+                            changed |= self.eliminate(index, 1 << (digit - 1));
+                        }
+                    }
+
+                    if changed {
+                        return true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    // cells of a house holding only as many digits as there are cells
+    fn naked_subset(&mut self, amount: u32) -> bool {
+        for house in 0..self.houses.len() {
+            let cells = self.houses[house];
+            let open = (0..SIZE)
+                .filter(|&j| (2..=amount).contains(&self.options[cells[j]].count_ones()))
+                .fold(0u16, |mask, j| mask | 1 << j);
+
+            for subset in self.subsets(amount) {
+                if subset & !open != 0 {
+                    continue;
+                }
+
+                let digits = (0..SIZE)
+                    .filter(|&j| subset & (1 << j) != 0)
+                    .fold(0, |mask, j| mask | self.options[cells[j]]);
+
+                if digits.count_ones() != amount {
+                    continue;
+                }
+
+                let mut changed = false;
+                for j in (0..SIZE).filter(|&j| subset & (1 << j) == 0) {
+                    changed |= self.eliminate(cells[j], digits);
+                }
+
+                if changed {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    // digits of a house that only fit in as many cells as there are digits
+    fn hidden_subset(&mut self, amount: u32) -> bool {
+        for house in 0..self.houses.len() {
+            let positions: Vec<u16> = (1..=SIZE).map(|d| self.positions(house, d)).collect();
+            let open = (0..SIZE)
+                .filter(|&d| positions[d] != 0)
+                .fold(0u16, |mask, d| mask | 1 << d);
+
+            for digits in self.subsets(amount) {
+                if digits & !open != 0 {
+                    continue;
+                }
+
+                let cells = (0..SIZE)
+                    .filter(|&d| digits & (1 << d) != 0)
+                    .fold(0u16, |mask, d| mask | positions[d]);
+
+                if cells.count_ones() != amount {
+                    continue;
+                }
+
+                let mut changed = false;
+                for j in (0..SIZE).filter(|&j| cells & (1 << j) != 0) {
+                    changed |= self.eliminate(self.houses[house][j], !digits);
+                }
+
+                if changed {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    // lines whose cells for the digit all fall in as many crossing lines as there are lines
+    fn fish(&mut self, amount: u32) -> bool {
+        for digit in 1..=SIZE {
+            for (base, cover) in [(0, SIZE), (SIZE, 0)] {
+                let positions: Vec<u16> =
+                    (0..SIZE).map(|i| self.positions(base + i, digit)).collect();
+                let open = (0..SIZE)
+                    .filter(|&i| (2..=amount).contains(&positions[i].count_ones()))
+                    .fold(0u16, |mask, i| mask | 1 << i);
+
+                for lines in self.subsets(amount) {
+                    if lines & !open != 0 {
+                        continue;
+                    }
+
+                    let crossing = (0..SIZE)
+                        .filter(|&i| lines & (1 << i) != 0)
+                        .fold(0u16, |mask, i| mask | positions[i]);
+
+                    if crossing.count_ones() != amount {
+                        continue;
+                    }
+
+                    let mut changed = false;
+                    for i in (0..SIZE).filter(|&i| crossing & (1 << i) != 0) {
+                        for j in (0..SIZE).filter(|&j| lines & (1 << j) == 0) {
+                            changed |= self.eliminate(self.houses[cover + i][j], 1 << (digit - 1));
+                        }
+                    }
+
+                    if changed {
+                        return true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    fn apply(&mut self, technique: Technique) -> bool {
+        match technique {
+            Technique::NakedSingle => self.naked_single(),
+            Technique::HiddenSingle => self.hidden_single(),
+            Technique::LockedCandidatesPointing => self.locked_candidates(true),
+            Technique::LockedCandidatesClaiming => self.locked_candidates(false),
+            Technique::NakedPair => self.naked_subset(2),
+            Technique::HiddenPair => self.hidden_subset(2),
+            Technique::NakedTriple => self.naked_subset(3),
+            Technique::HiddenTriple => self.hidden_subset(3),
+            Technique::XWing => self.fish(2),
+            Technique::Swordfish => self.fish(3),
+        }
+    }
+}
+
+// the score of the hardest technique the puzzle needs, always applying the easiest one that works
+fn score(puzzle: &Board) -> u32 {
+    let mut candidates = Candidates::new(puzzle);
+    // by position in `TECHNIQUES`, so it never depends on how `Technique` is ordered
+    let mut hardest: Option<usize> = None;
+
+    while let Some(level) = (0..TECHNIQUES.len()).find(|&i| candidates.apply(TECHNIQUES[i])) {
+        hardest = hardest.max(Some(level));
+    }
+
+    match (candidates.cells.contains(&0), hardest) {
+        (true, _) => UNSOLVED_SCORE,
+        (false, Some(level)) => technique_score(TECHNIQUES[level]),
+        (false, None) => 0,
+    }
+}
+
+/**
+A 9x9 grid filled by a depth first search, placing naked and hidden singles
+before every guess and guessing in the empty cell with the fewest options.
+*/
+struct FillGrid {
+    cells: [usize; SIZE * SIZE],
+    rows: [u16; SIZE],
+    cols: [u16; SIZE],
+    squares: [u16; SIZE],
+    // cell indices of row 0, col 0, square 0, row 1, ...
+    houses: Vec<[usize; SIZE]>,
+}
+
+impl FillGrid {
+    fn new() -> FillGrid {
+        let houses = (0..SIZE)
+            .flat_map(|i| {
+                let (start_row, start_col) =
+                    (i / SQUARE_SIZE * SQUARE_SIZE, i % SQUARE_SIZE * SQUARE_SIZE);
+                [
+                    std::array::from_fn(|col| i * SIZE + col),
+                    std::array::from_fn(|row| row * SIZE + i),
+                    std::array::from_fn(|j| {
+                        (start_row + j / SQUARE_SIZE) * SIZE + start_col + j % SQUARE_SIZE
+                    }),
+                ]
+            })
+            .collect();
+
+        FillGrid {
+            cells: [0; SIZE * SIZE],
+            rows: [0; SIZE],
+            cols: [0; SIZE],
+            squares: [0; SIZE],
+            houses,
+        }
+    }
+
+    fn square(index: usize) -> usize {
+        index / SIZE / SQUARE_SIZE * SQUARE_SIZE + index % SIZE / SQUARE_SIZE
+    }
+
+    fn options(&self, index: usize) -> u16 {
+        let used =
+            self.rows[index / SIZE] | self.cols[index % SIZE] | self.squares[Self::square(index)];
+        FULL_MASK & !used
+    }
+
+    fn set(&mut self, index: usize, value: usize) {
+        let bit = 1 << (value - 1);
+        self.cells[index] = value;
+        self.rows[index / SIZE] |= bit;
+        self.cols[index % SIZE] |= bit;
+        self.squares[Self::square(index)] |= bit;
+    }
+
+    fn unset(&mut self, index: usize) {
+        let bit = !(1 << (self.cells[index] - 1));
+        self.cells[index] = 0;
+        self.rows[index / SIZE] &= bit;
+        self.cols[index % SIZE] &= bit;
+        self.squares[Self::square(index)] &= bit;
+    }
+
+    fn propagate(&mut self, trail: &mut Vec<usize>) -> bool {
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for index in 0..self.cells.len() {
+                if self.cells[index] != 0 {
+                    continue;
+                }
+
+                let options = self.options(index);
+
+                if options == 0 {
+                    return false;
+                }
+
+                if options.count_ones() == 1 {
+                    self.set(index, options.trailing_zeros() as usize + 1);
+                    trail.push(index);
+                    changed = true;
+                }
+            }
+
+            for h in 0..self.houses.len() {
+                let (mut placed, mut once, mut more) = (0u16, 0u16, 0u16);
+
+                for &index in self.houses[h].iter() {
+                    if self.cells[index] != 0 {
+                        placed |= 1 << (self.cells[index] - 1);
+                    } else {
+                        let options = self.options(index);
+                        more |= once & options;
+                        once |= options;
+                    }
+                }
+
+                let missing = FULL_MASK & !placed;
+
+                if once & missing != missing {
+                    return false;
+                }
+
+                let singles = once & !more & missing;
+
+                if singles == 0 {
+                    continue;
+                }
+
+                let bit = singles & singles.wrapping_neg();
+                let index = *self.houses[h]
+                    .iter()
+                    .find(|&&i| self.cells[i] == 0 && self.options(i) & bit != 0)
+                    .unwrap();
+                self.set(index, bit.trailing_zeros() as usize + 1);
+                trail.push(index);
+                changed = true;
+            }
+        }
+
+        true
+    }
+
+    fn next_cell(&self) -> Option<(usize, u16)> {
+        let mut best: Option<(usize, u16)> = None;
+
+        for index in (0..self.cells.len()).filter(|&i| self.cells[i] == 0) {
+            let options = self.options(index);
+            let count = options.count_ones();
+
+            if best.is_none_or(|(_, o)| count < o.count_ones()) {
+                best = Some((index, options));
+
+                if count <= 1 {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+
+    fn fill_with<F>(&mut self, order: &mut F) -> bool
+    where
+        F: FnMut(&mut [usize]),
+    {
+        let mut trail: Vec<usize> = Vec::new();
+
+        if self.propagate(&mut trail) {
+            match self.next_cell() {
+                None => return true,
+                Some((index, options)) => {
+                    let mut values: Vec<usize> = (1..=SIZE)
+                        .filter(|v| options & (1 << (v - 1)) != 0)
+                        .collect();
+                    order(&mut values);
+
+                    for value in values {
+                        self.set(index, value);
+
+                        if self.fill_with(order) {
+                            return true;
+                        }
+
+                        self.unset(index);
+                    }
+                }
+            }
+        }
+
+        for index in trail.into_iter().rev() {
+            self.unset(index);
+        }
+
+        false
+    }
+}
+
+/**
+A full grid, trying the values of every guess in the order `order` leaves them in.
+*/
+fn fill<F>(order: &mut F) -> StrResult<Board>
+where
+    F: FnMut(&mut [usize]),
+{
+    let mut grid = FillGrid::new();
+
+    if !grid.fill_with(order) {
+        return Err("Empty board has no solution".to_string());
+    }
+
+    Board::from(&grid.cells.chunks(SIZE).map(|row| row.to_vec()).collect())
+}
+
+// every cell of the board, by row
+fn cells() -> Vec<(usize, usize)> {
+    (0..SIZE)
+        .flat_map(|row| (0..SIZE).map(move |col| (row, col)))
+        .collect()
+}
+
+/**
+Removes the givens of `puzzle` in the order of `cells`, keeping the ones whose removal
+would leave several solutions or score the puzzle above `max_score`.
+*/
+fn remove_givens(puzzle: &mut Board, cells: &[(usize, usize)], max_score: u32) -> StrResult<()> {
+    for &(row, col) in cells {
+        let value = *puzzle.at(row, col).unwrap();
+        puzzle.set(row, col, 0)?;
+
+        if !(has_unique_solution(puzzle)? && score(puzzle) <= max_score) {
+            puzzle.set(row, col, value)?;
+        }
+    }
+
+    Ok(())
+}
+
+/**
+Tries up to `ATTEMPTS` grids, removing givens in a random order while the puzzle stays
+unique and not above the difficulty, and takes the first one scored within the difficulty.
+@returns an error when no grid made it, rather than a puzzle of another difficulty.
+*/
+pub(super) fn generate(key: &str, difficulty: Difficulty) -> StrResult<Puzzle> {
+    let seed = hash_key(&format!("{DAILY_V1}\0{}\0{key}", difficulty_id(difficulty)));
+    let mut rng = DailyRng(seed);
+    let (min_score, max_score) = score_range(difficulty);
+
+    for _ in 0..ATTEMPTS {
+        let solution = fill(&mut |values| rng.shuffle(values))?;
+
+        let mut removal_order = cells();
+        rng.shuffle(&mut removal_order);
+
+        let mut puzzle = solution.clone();
+        remove_givens(&mut puzzle, &removal_order, max_score)?;
+
+        if (min_score..=max_score).contains(&score(&puzzle)) {
+            return Ok(Puzzle {
+                rating: Some(rate(&puzzle)?),
+                puzzle,
+                solution,
+            });
+        }
+    }
+
+    Err(format!(
+        "Could not make a {} daily puzzle for `{key}`",
+        difficulty_id(difficulty)
+    ))
+}
@@ -0,0 +1,239 @@
+use crate::board::Board;
+use crate::generate::{sample_grid, GridSampling, Symmetry};
+use crate::solve::unique_within;
+use crate::steps::{rate, Difficulty, Rating};
+use crate::types::StrResult;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+// attempts made for a difficulty target when `max_attempts` is not given
+pub(crate) const DEFAULT_MAX_ATTEMPTS: usize = 50;
+
+// search nodes a uniqueness check may visit before the given is kept instead.
+// 9x9 checks take a few dozen at most, some 16x16 ones would take seconds
+pub(crate) const REMOVAL_MAX_NODES: usize = 2_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DifficultyTarget {
+    Tier(Difficulty),
+    Score(RangeInclusive<u32>),
+}
+
+impl DifficultyTarget {
+    pub fn score_range(&self) -> RangeInclusive<u32> {
+        match self {
+            DifficultyTarget::Tier(difficulty) => difficulty.score_range(),
+            DifficultyTarget::Score(range) => range.clone(),
+        }
+    }
+}
+
+/**
+- `seed` - makes the generation repeatable, a random seed is used when `None`.
+- `target` - the difficulty the puzzle's rating must fall in.
+- `max_attempts` - how many full boards to try before giving up on the target.
+- `time_limit` - gives up on the target after this long. not supported on wasm.
+- `symmetry` - the symmetry the clue layout keeps.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct GenerateOptions {
+    pub seed: Option<u64>,
+    pub symmetry: Symmetry,
+    pub target: Option<DifficultyTarget>,
+    pub max_attempts: Option<usize>,
+    pub time_limit: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    pub puzzle: Board,
+    pub solution: Board,
+    // only rated when generating for a difficulty target
+    pub rating: Option<Rating>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) struct Deadline(Option<std::time::Instant>);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Deadline {
+    pub(crate) fn new(time_limit: Option<Duration>) -> StrResult<Self> {
+        Ok(Deadline(
+            time_limit.map(|limit| std::time::Instant::now() + limit),
+        ))
+    }
+
+    pub(crate) fn passed(&self) -> bool {
+        self.0
+            .is_some_and(|deadline| std::time::Instant::now() >= deadline)
+    }
+}
+
+// std has no clock on wasm32-unknown-unknown
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct Deadline;
+
+#[cfg(target_arch = "wasm32")]
+impl Deadline {
+    pub(crate) fn new(time_limit: Option<Duration>) -> StrResult<Self> {
+        match time_limit {
+            Some(_) => Err("Time limits are not supported on wasm, use max_attempts".to_string()),
+            None => Ok(Deadline),
+        }
+    }
+
+    pub(crate) fn passed(&self) -> bool {
+        false
+    }
+}
+
+pub(crate) fn options_rng(options: &GenerateOptions) -> StdRng {
+    match options.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+pub(crate) fn shuffled_orbits<R: Rng + ?Sized>(
+    symmetry: Symmetry,
+    size: usize,
+    rng: &mut R,
+) -> Vec<Vec<(usize, usize)>> {
+    let mut orbits = symmetry.orbits(size);
+    orbits.shuffle(rng);
+    orbits
+}
+
+/**
+Removes the givens of `puzzle` one orbit at a time, as long as the solution stays unique.
+Givens whose uniqueness check takes too long are kept, so big boards may keep a few more.
+- `orbits` - the cells removed together, in the order they are tried.
+- `max_score` - keeps every given whose removal would rate the puzzle above it.
+*/
+pub(crate) fn remove_givens(
+    puzzle: &mut Board,
+    orbits: Vec<Vec<(usize, usize)>>,
+    max_score: Option<u32>,
+) -> StrResult<()> {
+    remove_givens_while(
+        puzzle,
+        orbits,
+        REMOVAL_MAX_NODES,
+        &mut |puzzle| match max_score {
+            Some(max_score) => Ok(rate(puzzle)?.score <= max_score),
+            None => Ok(true),
+        },
+    )
+}
+
+/**
+Like `remove_givens`, but keeps every given whose removal `accept` rejects.
+`accept` only sees puzzles that still have a unique solution.
+Removal gives up on slow givens: a given whose uniqueness check visits more than
+`max_nodes` nodes is kept even when it could go, so the puzzle may not be minimal.
+*/
+pub(crate) fn remove_givens_while<F>(
+    puzzle: &mut Board,
+    orbits: Vec<Vec<(usize, usize)>>,
+    max_nodes: usize,
+    accept: &mut F,
+) -> StrResult<()>
+where
+    F: FnMut(&Board) -> StrResult<bool>,
+{
+    for orbit in orbits {
+        let values: Vec<usize> = orbit
+            .iter()
+            .map(|&(row, col)| *puzzle.at(row, col).unwrap())
+            .collect();
+
+        if values.iter().all(|&v| v == 0) {
+            continue;
+        }
+
+        for &(row, col) in orbit.iter() {
+            puzzle.set(row, col, 0)?;
+        }
+
+        if !(unique_within(puzzle, max_nodes)? == Some(true) && accept(puzzle)?) {
+            for (&(row, col), &value) in orbit.iter().zip(values.iter()) {
+                puzzle.set(row, col, value)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn generate_for_target<R: Rng + ?Sized>(
+    size: usize,
+    target: &DifficultyTarget,
+    options: &GenerateOptions,
+    rng: &mut R,
+) -> StrResult<Puzzle> {
+    let range = target.score_range();
+    let max_attempts = options.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS);
+    let deadline = Deadline::new(options.time_limit)?;
+
+    for _ in 0..max_attempts {
+        if deadline.passed() {
+            break;
+        }
+
+        let solution = sample_grid(size, GridSampling::Shuffled, rng)?;
+        let mut puzzle = solution.clone();
+
+        remove_givens(
+            &mut puzzle,
+            shuffled_orbits(options.symmetry, size, rng),
+            Some(*range.end()),
+        )?;
+
+        let rating = rate(&puzzle)?;
+
+        if range.contains(&rating.score) {
+            return Ok(Puzzle {
+                puzzle,
+                solution,
+                rating: Some(rating),
+            });
+        }
+    }
+
+    Err(format!(
+        "Could not generate a puzzle rated {}..={} within the given attempts",
+        range.start(),
+        range.end()
+    ))
+}
+
+/**
+Generates a puzzle with exactly one solution by filling a random board
+and removing givens while the solution stays unique.
+When a target is given, boards are retried until the rating falls in its range.
+*/
+pub fn generate(size: usize, options: &GenerateOptions) -> StrResult<Puzzle> {
+    let mut rng = options_rng(options);
+
+    if let Some(target) = &options.target {
+        return generate_for_target(size, target, options, &mut rng);
+    }
+
+    let solution = sample_grid(size, GridSampling::Shuffled, &mut rng)?;
+    let mut puzzle = solution.clone();
+
+    remove_givens(
+        &mut puzzle,
+        shuffled_orbits(options.symmetry, size, &mut rng),
+        None,
+    )?;
+
+    Ok(Puzzle {
+        puzzle,
+        solution,
+        rating: None,
+    })
+}
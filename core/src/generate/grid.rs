@@ -0,0 +1,76 @@
+use crate::board::Board;
+use crate::solve::random_completion;
+use crate::transform::Transform;
+use crate::types::StrResult;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/**
+How full grids are sampled.
+- `Search` - a randomized search, which favours some fills over others.
+- `Shuffled` - a searched grid under a random relabeling, transpose and row, col, band
+  and stack permutations, which gets close to a uniform pick over all grids.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GridSampling {
+    Search,
+    #[default]
+    Shuffled,
+}
+
+// a random order of the lines, keeping the lines of every band (or stack) together
+fn band_permutation<R: Rng + ?Sized>(size: usize, square_size: usize, rng: &mut R) -> Vec<usize> {
+    let mut bands: Vec<usize> = (0..square_size).collect();
+    bands.shuffle(rng);
+
+    bands
+        .into_iter()
+        .flat_map(|band| {
+            let mut lines: Vec<usize> = (band * square_size..(band + 1) * square_size).collect();
+            lines.shuffle(rng);
+            lines
+        })
+        .take(size)
+        .collect()
+}
+
+fn shuffle_grid<R: Rng + ?Sized>(grid: &Board, rng: &mut R) -> StrResult<Board> {
+    let size = grid.get_size();
+    let square_size = grid.get_square_size();
+
+    let rows = band_permutation(size, square_size, rng);
+    let cols = band_permutation(size, square_size, rng);
+    let transpose = rng.gen_bool(0.5);
+    let mut digits: Vec<usize> = (1..=size).collect();
+    digits.shuffle(rng);
+
+    Transform::from_parts(transpose, rows, cols, digits)?.apply(grid)
+}
+
+pub(crate) fn sample_grid<R: Rng + ?Sized>(
+    size: usize,
+    sampling: GridSampling,
+    rng: &mut R,
+) -> StrResult<Board> {
+    let grid = random_completion(&Board::new(size)?, rng)?
+        .ok_or(format!("Board of size {size} has no solution"))?;
+
+    match sampling {
+        GridSampling::Search => Ok(grid),
+        GridSampling::Shuffled => shuffle_grid(&grid, rng),
+    }
+}
+
+/**
+@returns a random full valid board.
+@param seed makes the grid repeatable, a random seed is used when `None`.
+*/
+pub fn random_grid(size: usize, sampling: GridSampling, seed: Option<u64>) -> StrResult<Board> {
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    sample_grid(size, sampling, &mut rng)
+}
@@ -0,0 +1,94 @@
+use crate::board::Board;
+use crate::generate::{options_rng, sample_grid, Deadline, GenerateOptions, GridSampling, Puzzle};
+use crate::solve::{count_solutions, random_completion};
+use crate::types::StrResult;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+// grids tried for a mask when `max_attempts` is not given
+const DEFAULT_MASK_ATTEMPTS: usize = 500;
+
+// solutions counted past this are all as bad as each other
+const SOLUTIONS_CAP: usize = 64;
+
+fn restrict(solution: &Board, mask: &Board<bool>) -> StrResult<Board> {
+    let mut puzzle = Board::new(solution.get_size())?;
+
+    for cell in mask.filter(|&given| given) {
+        puzzle.set(
+            cell.row,
+            cell.col,
+            *solution.at(cell.row, cell.col).unwrap(),
+        )?;
+    }
+
+    Ok(puzzle)
+}
+
+// re-fills a few random givens of the puzzle, keeping the rest of them
+fn neighbour<R: Rng + ?Sized>(
+    puzzle: &Board,
+    givens: &[(usize, usize)],
+    rng: &mut R,
+) -> StrResult<Board> {
+    let mut relaxed = puzzle.clone();
+    let amount = (givens.len() / 8).max(2).min(givens.len());
+
+    for &(row, col) in givens.choose_multiple(rng, amount) {
+        relaxed.set(row, col, 0)?;
+    }
+
+    random_completion(&relaxed, rng)?.ok_or("Relaxed puzzle has no solution".to_string())
+}
+
+/**
+Generates a puzzle whose givens are exactly the cells set in `mask`.
+Starts from a random full board and keeps re-filling a few of the masked cells,
+moving to the new board whenever the masked puzzle has no more solutions than before.
+`max_attempts` counts the boards tried, `symmetry` and `target` are ignored.
+*/
+pub fn generate_from_mask(mask: &Board<bool>, options: &GenerateOptions) -> StrResult<Puzzle> {
+    let size = mask.get_size();
+    let mut rng = options_rng(options);
+    let max_attempts = options.max_attempts.unwrap_or(DEFAULT_MASK_ATTEMPTS);
+    let deadline = Deadline::new(options.time_limit)?;
+
+    let givens: Vec<(usize, usize)> = mask
+        .filter(|&given| given)
+        .iter()
+        .map(|cell| (cell.row, cell.col))
+        .collect();
+
+    let mut solution = sample_grid(size, GridSampling::Shuffled, &mut rng)?;
+    let mut puzzle = restrict(&solution, mask)?;
+    let mut count = count_solutions(&puzzle, SOLUTIONS_CAP)?;
+
+    let mut attempts = 0;
+
+    while count > 1 && attempts < max_attempts && !deadline.passed() && !givens.is_empty() {
+        attempts += 1;
+
+        let candidate = neighbour(&puzzle, &givens, &mut rng)?;
+        let candidate_puzzle = restrict(&candidate, mask)?;
+        let candidate_count = count_solutions(&candidate_puzzle, SOLUTIONS_CAP)?;
+
+        if candidate_count <= count {
+            solution = candidate;
+            puzzle = candidate_puzzle;
+            count = candidate_count;
+        }
+    }
+
+    if count != 1 {
+        return Err(format!(
+            "Could not find a unique puzzle for the {} givens of the mask within the given attempts",
+            givens.len()
+        ));
+    }
+
+    Ok(Puzzle {
+        puzzle,
+        solution,
+        rating: None,
+    })
+}
@@ -0,0 +1,62 @@
+use crate::board::Board;
+use crate::generate::{remove_givens_while, shuffled_orbits, Symmetry};
+use crate::solve::{count_solutions, has_unique_solution};
+use crate::types::{PositionalValue, StrResult};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn ensure_unique(board: &Board) -> StrResult<()> {
+    match count_solutions(board, 2)? {
+        0 => Err("Board has no solution".to_string()),
+        1 => Ok(()),
+        _ => Err("Board has more than one solution".to_string()),
+    }
+}
+
+/**
+@returns the givens that can be removed on their own while the solution stays unique.
+*/
+pub fn redundant_givens(board: &Board) -> StrResult<Vec<PositionalValue<usize>>> {
+    ensure_unique(board)?;
+
+    let mut puzzle = board.clone();
+    let mut redundant = Vec::new();
+
+    for cell in board.filter(|&v| v != 0) {
+        puzzle.set(cell.row, cell.col, 0)?;
+
+        if has_unique_solution(&puzzle)? {
+            redundant.push(PositionalValue::new(cell.row, cell.col, *cell.value));
+        }
+
+        puzzle.set(cell.row, cell.col, *cell.value)?;
+    }
+
+    Ok(redundant)
+}
+
+/**
+@returns whether every given of the board is needed for its solution to be unique.
+*/
+pub fn is_minimal(board: &Board) -> StrResult<bool> {
+    Ok(redundant_givens(board)?.is_empty())
+}
+
+/**
+Removes redundant givens until the puzzle is minimal.
+Different seeds may keep different givens.
+*/
+pub fn reduce_to_minimal(board: &Board, seed: u64) -> StrResult<Board> {
+    ensure_unique(board)?;
+
+    let mut puzzle = board.clone();
+    let orbits = shuffled_orbits(
+        Symmetry::None,
+        board.get_size(),
+        &mut StdRng::seed_from_u64(seed),
+    );
+    // no node budget, a given that could go is never kept for being slow to check
+    remove_givens_while(&mut puzzle, orbits, usize::MAX, &mut |_| Ok(true))?;
+
+    Ok(puzzle)
+}
@@ -0,0 +1,19 @@
+pub use daily::*;
+pub use generate::*;
+pub use grid::*;
+pub use mask::*;
+pub use minimal::*;
+pub use showcase::*;
+// kept here too, since generating is where most callers pick one
+pub use crate::transform::Symmetry;
+
+mod daily;
+mod daily_v1;
+mod generate;
+mod grid;
+mod mask;
+mod minimal;
+mod showcase;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,97 @@
+use crate::board::Board;
+use crate::generate::{
+    options_rng, remove_givens_while, sample_grid, shuffled_orbits, Deadline, GenerateOptions,
+    GridSampling, Puzzle, DEFAULT_MAX_ATTEMPTS, REMOVAL_MAX_NODES,
+};
+use crate::steps::{rate, trace_solve_with, Technique};
+use crate::types::StrResult;
+
+/**
+A puzzle made to practice one technique.
+- `technique` - the technique the solve can't do without.
+- `others` - the only other techniques the solve may use.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Showcase {
+    pub technique: Technique,
+    pub others: Vec<Technique>,
+}
+
+impl Showcase {
+    /**
+    A showcase where the other steps use nothing harder than `hardest_other`.
+    */
+    pub fn up_to(technique: Technique, hardest_other: Technique) -> Showcase {
+        Showcase {
+            technique,
+            others: Technique::ALL
+                .into_iter()
+                .filter(|&other| other <= hardest_other && other != technique)
+                .collect(),
+        }
+    }
+
+    fn allowed(&self) -> Vec<Technique> {
+        let mut allowed = self.others.clone();
+        allowed.push(self.technique);
+        allowed.sort();
+        allowed
+    }
+
+    /**
+    @returns whether the board is solved by the showcase's techniques, and not without its technique.
+    */
+    pub fn is_shown_by(&self, board: &Board) -> StrResult<bool> {
+        Ok(trace_solve_with(board, &self.allowed())?.solved
+            && !trace_solve_with(board, &self.others)?.solved)
+    }
+}
+
+/**
+Generates a puzzle whose step by step solve needs `showcase.technique`,
+and nothing but it and `showcase.others`.
+Givens are removed while the puzzle stays solvable with those techniques,
+and boards are retried until the technique can't be left out.
+Showcases of the harder techniques come up rarely and may need a few hundred attempts.
+Like `remove_givens_while`, givens whose uniqueness check is slow are kept.
+The rating of the puzzle is always filled in, `target` is ignored.
+*/
+pub fn generate_showcase(
+    size: usize,
+    showcase: &Showcase,
+    options: &GenerateOptions,
+) -> StrResult<Puzzle> {
+    let mut rng = options_rng(options);
+    let max_attempts = options.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS);
+    let deadline = Deadline::new(options.time_limit)?;
+    let allowed = showcase.allowed();
+
+    for _ in 0..max_attempts {
+        if deadline.passed() {
+            break;
+        }
+
+        let solution = sample_grid(size, GridSampling::Shuffled, &mut rng)?;
+        let mut puzzle = solution.clone();
+
+        remove_givens_while(
+            &mut puzzle,
+            shuffled_orbits(options.symmetry, size, &mut rng),
+            REMOVAL_MAX_NODES,
+            &mut |puzzle| Ok(trace_solve_with(puzzle, &allowed)?.solved),
+        )?;
+
+        if !trace_solve_with(&puzzle, &showcase.others)?.solved {
+            return Ok(Puzzle {
+                rating: Some(rate(&puzzle)?),
+                puzzle,
+                solution,
+            });
+        }
+    }
+
+    Err(format!(
+        "Could not generate a puzzle that needs {} within the given attempts",
+        showcase.technique.name()
+    ))
+}
@@ -0,0 +1,381 @@
+use crate::board::Board;
+use crate::generate::{
+    daily_puzzle, generate, generate_from_mask, generate_showcase, is_minimal, random_grid,
+    reduce_to_minimal, redundant_givens, DifficultyTarget, GenerateOptions, GridSampling, Showcase,
+    Symmetry, DAILY_V1,
+};
+use crate::solve::{count_solutions, find_solutions};
+use crate::steps::{rate, trace_solve_with, Difficulty, Technique};
+use crate::test_utils::{board_from_str, EASY_PUZZLE};
+use crate::types::StrResult;
+use crate::validators::is_valid_sudoku;
+use std::collections::HashSet;
+
+fn seeded(seed: u64) -> GenerateOptions {
+    GenerateOptions {
+        seed: Some(seed),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn generated_puzzle_has_unique_solution() -> StrResult<()> {
+    for size in [4, 9] {
+        let generated = generate(size, &seeded(7))?;
+
+        assert!(generated.solution.is_full());
+        assert!(is_valid_sudoku(&generated.solution));
+        assert_eq!(count_solutions(&generated.puzzle, 2)?, 1);
+        assert_eq!(
+            find_solutions(&generated.puzzle, 1)?[0].get_rows(),
+            generated.solution.get_rows()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn should_generate_16x16_puzzle() -> StrResult<()> {
+    // uniqueness checks give up on slow givens, keeping this well under a second in release
+    let generated = generate(16, &seeded(4))?;
+
+    assert!(is_valid_sudoku(&generated.solution));
+    assert_eq!(count_solutions(&generated.puzzle, 2)?, 1);
+    Ok(())
+}
+
+#[test]
+fn generated_puzzle_is_repeatable() -> StrResult<()> {
+    let first = generate(9, &seeded(42))?;
+    let second = generate(9, &seeded(42))?;
+
+    assert_eq!(first.puzzle.get_rows(), second.puzzle.get_rows());
+    Ok(())
+}
+
+#[test]
+fn fail_generating_invalid_size() {
+    assert!(generate(10, &GenerateOptions::default()).is_err());
+}
+
+#[test]
+fn should_generate_requested_tier() -> StrResult<()> {
+    for difficulty in [Difficulty::Easy, Difficulty::Medium] {
+        let options = GenerateOptions {
+            target: Some(DifficultyTarget::Tier(difficulty)),
+            ..seeded(5)
+        };
+
+        let generated = generate(9, &options)?;
+
+        assert_eq!(rate(&generated.puzzle)?.difficulty, difficulty);
+        assert_eq!(count_solutions(&generated.puzzle, 2)?, 1);
+    }
+    Ok(())
+}
+
+#[test]
+fn fail_generating_impossible_score() {
+    let options = GenerateOptions {
+        target: Some(DifficultyTarget::Score(2000..=2000)),
+        max_attempts: Some(1),
+        ..seeded(5)
+    };
+
+    assert!(generate(4, &options).is_err());
+}
+
+#[test]
+fn generated_clues_keep_symmetry() -> StrResult<()> {
+    for symmetry in Symmetry::ALL {
+        let options = GenerateOptions {
+            symmetry,
+            ..seeded(11)
+        };
+
+        let generated = generate(9, &options)?;
+
+        assert!(symmetry.matches_clues(&generated.puzzle));
+        assert_eq!(count_solutions(&generated.puzzle, 2)?, 1);
+    }
+    Ok(())
+}
+
+#[test]
+fn should_split_cells_to_orbits() {
+    let orbits = Symmetry::Rotational90.orbits(9);
+
+    assert_eq!(
+        Symmetry::Rotational90.orbit(0, 1, 9),
+        vec![(0, 1), (1, 8), (8, 7), (7, 0)]
+    );
+    assert_eq!(orbits.iter().map(|o| o.len()).sum::<usize>(), 81);
+    assert_eq!(orbits.iter().filter(|o| o.len() == 1).count(), 1);
+}
+
+fn mask_from_str(mask: &str) -> Board<bool> {
+    let data: Vec<Vec<bool>> = mask
+        .split_whitespace()
+        .map(|row| row.chars().map(|c| c == 'x').collect())
+        .collect();
+
+    Board::from(&data).unwrap()
+}
+
+#[test]
+fn should_generate_from_mask() -> StrResult<()> {
+    // a heart
+    let mask = mask_from_str(
+        ".xx...xx.
+         x..x.x..x
+         x...x...x
+         x.......x
+         .x.xxx.x.
+         ..x...x..
+         ...x.x...
+         ....x....
+         .xx...xx.",
+    );
+
+    let generated = generate_from_mask(&mask, &seeded(3))?;
+
+    assert_eq!(count_solutions(&generated.puzzle, 2)?, 1);
+    for cell in generated.puzzle.get_flat() {
+        assert_eq!(*cell.value != 0, *mask.at(cell.row, cell.col).unwrap());
+    }
+    Ok(())
+}
+
+#[test]
+fn should_fail_mask_with_too_few_givens() -> StrResult<()> {
+    let mask = mask_from_str(
+        "x........
+         .x.......
+         ..x......
+         .........
+         .........
+         .........
+         .........
+         .........
+         .........",
+    );
+
+    let options = GenerateOptions {
+        max_attempts: Some(20),
+        ..seeded(3)
+    };
+
+    assert!(generate_from_mask(&mask, &options).is_err());
+    Ok(())
+}
+
+#[test]
+fn generated_puzzle_is_minimal() -> StrResult<()> {
+    let generated = generate(9, &seeded(7))?;
+
+    assert!(is_minimal(&generated.puzzle)?);
+    Ok(())
+}
+
+#[test]
+fn should_reduce_to_minimal() -> StrResult<()> {
+    let board = board_from_str(EASY_PUZZLE);
+
+    assert!(!is_minimal(&board)?);
+    assert!(!redundant_givens(&board)?.is_empty());
+
+    let reduced = reduce_to_minimal(&board, 1)?;
+
+    assert!(is_minimal(&reduced)?);
+    assert_eq!(count_solutions(&reduced, 2)?, 1);
+    for cell in reduced.filter(|&v| v != 0) {
+        assert_eq!(board.at(cell.row, cell.col), Some(cell.value));
+    }
+    Ok(())
+}
+
+#[test]
+fn should_reduce_16x16_to_minimal() -> StrResult<()> {
+    // generating this one keeps a redundant given whose uniqueness check runs out of nodes
+    let generated = generate(16, &seeded(5))?;
+
+    assert!(!is_minimal(&generated.puzzle)?);
+
+    let reduced = reduce_to_minimal(&generated.puzzle, 1)?;
+
+    assert!(is_minimal(&reduced)?);
+    assert_eq!(count_solutions(&reduced, 2)?, 1);
+    Ok(())
+}
+
+#[test]
+fn minimality_needs_unique_solution() -> StrResult<()> {
+    assert!(is_minimal(&Board::new(4)?).is_err());
+    Ok(())
+}
+
+#[test]
+fn random_grid_is_full_and_valid() -> StrResult<()> {
+    for size in [1, 4, 9, 16] {
+        for sampling in [GridSampling::Search, GridSampling::Shuffled] {
+            let grid = random_grid(size, sampling, Some(9))?;
+
+            assert!(grid.is_full());
+            assert!(is_valid_sudoku(&grid));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn random_grid_is_repeatable() -> StrResult<()> {
+    let first = random_grid(9, GridSampling::Shuffled, Some(4))?;
+    let second = random_grid(9, GridSampling::Shuffled, Some(4))?;
+    let other = random_grid(9, GridSampling::Shuffled, Some(5))?;
+
+    assert_eq!(first.get_rows(), second.get_rows());
+    assert_ne!(first.get_rows(), other.get_rows());
+    Ok(())
+}
+
+#[test]
+fn shuffled_grids_cover_small_boards() -> StrResult<()> {
+    let mut seen = HashSet::new();
+
+    for seed in 0..5000 {
+        seen.insert(
+            random_grid(4, GridSampling::Shuffled, Some(seed))?
+                .get_rows()
+                .clone(),
+        );
+    }
+
+    // all 288 grids of size 4
+    assert_eq!(seen.len(), 288);
+    Ok(())
+}
+
+// pinned, a change here breaks every daily puzzle already handed out
+fn assert_daily(key: &str, difficulty: Difficulty, puzzle: &str) -> StrResult<()> {
+    let daily = daily_puzzle(DAILY_V1, key, difficulty)?;
+
+    assert_eq!(
+        daily.puzzle.get_rows(),
+        board_from_str(puzzle).get_rows(),
+        "{key} {difficulty:?}"
+    );
+    assert_eq!(rate(&daily.puzzle)?.difficulty, difficulty, "{key}");
+    let solutions = find_solutions(&daily.puzzle, 2)?;
+    assert_eq!(solutions.len(), 1);
+    assert_eq!(solutions[0].get_rows(), daily.solution.get_rows());
+    Ok(())
+}
+
+#[test]
+fn daily_puzzle_is_stable() -> StrResult<()> {
+    let daily = daily_puzzle(DAILY_V1, "2026-10-18", Difficulty::Easy)?;
+
+    assert_eq!(
+        daily.solution.get_rows(),
+        board_from_str(
+            "639728145142356987578914623786541392425839761913267458364182579257493816891675234"
+        )
+        .get_rows()
+    );
+
+    for (key, difficulty, puzzle) in [
+        (
+            "2026-10-18",
+            Difficulty::Easy,
+            "030008045100006080000910003006500092400030000003000008004000500200000006800600230",
+        ),
+        (
+            "2027-01-01",
+            Difficulty::Easy,
+            "800070000020003000030000507000080000300000675090000403080020000047008106009054000",
+        ),
+        (
+            "2026-10-18",
+            Difficulty::Medium,
+            "409001005000700000000495013002000040031040070000050030000002000310000068008300000",
+        ),
+        (
+            "2026-10-18",
+            Difficulty::Extreme,
+            "002000000078006100400200390000608000080050400105000002000000003200800009803100005",
+        ),
+        (
+            "2027-01-01",
+            Difficulty::Extreme,
+            "003051060800400300007000001048000690000080000090000007070010000061000400000069003",
+        ),
+    ] {
+        assert_daily(key, difficulty, puzzle)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn hard_daily_puzzles_are_stable() -> StrResult<()> {
+    assert_daily(
+        "2026-10-18",
+        Difficulty::Hard,
+        "000000007507000600016000950060100000000962004005000300000206080000080700320700000",
+    )?;
+    assert_daily(
+        "2026-10-18",
+        Difficulty::Expert,
+        "000700000007009000060050002401000000000020008280040730900200400006010800100400050",
+    )?;
+    // expert puzzles are rare, this one takes about 170 grids
+    assert_daily(
+        "2026-10-19",
+        Difficulty::Expert,
+        "070530000000890000008000400060070209802000034000000061030000007007083190000620000",
+    )
+}
+
+#[test]
+fn daily_puzzle_depends_on_key_and_difficulty() -> StrResult<()> {
+    let easy = daily_puzzle(DAILY_V1, "2026-10-18", Difficulty::Easy)?;
+    let next_day = daily_puzzle(DAILY_V1, "2026-10-19", Difficulty::Easy)?;
+    let daily = daily_puzzle(DAILY_V1, "2026-10-18", Difficulty::Medium)?;
+
+    assert_ne!(easy.puzzle.get_rows(), next_day.puzzle.get_rows());
+    assert_ne!(easy.solution.get_rows(), daily.solution.get_rows());
+    assert_eq!(daily.rating.unwrap().difficulty, Difficulty::Medium);
+    assert_eq!(count_solutions(&daily.puzzle, 2)?, 1);
+    assert!(daily_puzzle("daily-v0", "2026-10-18", Difficulty::Medium).is_err());
+    Ok(())
+}
+
+#[test]
+fn showcase_needs_its_technique() -> StrResult<()> {
+    let showcase = Showcase::up_to(Technique::LockedCandidatesPointing, Technique::HiddenSingle);
+    let generated = generate_showcase(9, &showcase, &seeded(2))?;
+
+    assert!(showcase.is_shown_by(&generated.puzzle)?);
+    assert!(
+        !Showcase::up_to(Technique::LockedCandidatesPointing, Technique::NakedSingle)
+            .is_shown_by(&board_from_str(EASY_PUZZLE))?
+    );
+    assert_eq!(count_solutions(&generated.puzzle, 2)?, 1);
+    Ok(())
+}
+
+#[test]
+fn should_generate_x_wing_showcase() -> StrResult<()> {
+    let showcase = Showcase::up_to(Technique::XWing, Technique::HiddenTriple);
+    let options = GenerateOptions {
+        max_attempts: Some(2_000),
+        ..seeded(1)
+    };
+    let generated = generate_showcase(9, &showcase, &options)?;
+    let trace = trace_solve_with(&generated.puzzle, &Technique::ALL)?;
+
+    assert!(showcase.is_shown_by(&generated.puzzle)?);
+    assert!(trace.solved);
+    assert_eq!(trace.hardest_technique(), Some(Technique::XWing));
+    assert_eq!(count_solutions(&generated.puzzle, 2)?, 1);
+    Ok(())
+}
@@ -1,6 +1,15 @@
-mod analyze;
+pub mod analyze;
 pub mod board;
+mod combinatorics;
+pub mod contradiction;
+pub mod diagnose;
+pub mod explain;
+pub mod generate;
 mod infer;
 pub mod solve;
-mod types;
+pub mod steps;
+#[cfg(test)]
+mod test_utils;
+pub mod transform;
+pub mod types;
 pub mod validators;
@@ -0,0 +1,88 @@
+use crate::board::{Board, BoardData};
+use crate::solve::find_solutions;
+use crate::types::{PositionalValue, StrResult};
+
+/**
+What every solution of a board agrees on.
+- `fixed` - the empty cells that take the same value in every solution.
+- `possible` - the values each cell takes in some solution, sorted. A given only has its own value.
+*/
+#[derive(Debug, Clone)]
+pub struct Backbone {
+    pub fixed: Vec<PositionalValue<usize>>,
+    pub possible: Board<Vec<usize>>,
+}
+
+impl Backbone {
+    // the empty cells the solutions disagree on
+    pub fn ambiguous(&self) -> Vec<(usize, usize)> {
+        self.possible
+            .filter(|values| values.len() > 1)
+            .iter()
+            .map(|cell| (cell.row, cell.col))
+            .collect()
+    }
+}
+
+fn mark_values(solution: &Board, seen: &mut [Vec<Vec<bool>>]) {
+    for cell in solution.get_flat() {
+        seen[cell.row][cell.col][cell.value - 1] = true;
+    }
+}
+
+/**
+Finds the backbone of the board without listing all of its solutions:
+every solution found marks all its values as possible, and a solution is only
+searched for a value of a cell that no solution has shown yet.
+*/
+pub fn find_backbone(board: &Board) -> StrResult<Backbone> {
+    let size = board.get_size();
+
+    let first = find_solutions(board, 1)?
+        .pop()
+        .ok_or("Board has no solution".to_string())?;
+
+    // seen[row][col][value - 1]
+    let mut seen = vec![vec![vec![false; size]; size]; size];
+    mark_values(&first, &mut seen);
+
+    for row in 0..size {
+        for col in 0..size {
+            if *board.at(row, col).unwrap() != 0 {
+                continue;
+            }
+
+            for value in 1..=size {
+                if seen[row][col][value - 1] {
+                    continue;
+                }
+
+                let mut tried = board.clone();
+                tried.set(row, col, value)?;
+
+                if let Some(solution) = find_solutions(&tried, 1)?.pop() {
+                    mark_values(&solution, &mut seen);
+                }
+            }
+        }
+    }
+
+    let possible_data: BoardData<Vec<usize>> = seen
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|values| (1..=size).filter(|&v| values[v - 1]).collect())
+                .collect()
+        })
+        .collect();
+    let possible = Board::from(&possible_data)?;
+
+    let fixed = possible
+        .filter(|values| values.len() == 1)
+        .into_iter()
+        .filter(|cell| *board.at(cell.row, cell.col).unwrap() == 0)
+        .map(|cell| PositionalValue::new(cell.row, cell.col, cell.value[0]))
+        .collect();
+
+    Ok(Backbone { fixed, possible })
+}
@@ -0,0 +1,84 @@
+use crate::analyze::{analyze_board, is_full_board, update_board, AnalyzedBoard};
+use crate::board::Board;
+use crate::combinatorics::combinations;
+use crate::solve::{find_solutions, place_singles};
+use crate::types::{PositionalValue, StrResult};
+
+/**
+- `size` - the number of assignments in each backdoor, 0 when singles already solve the board.
+- `sets` - every backdoor of that size, with the values of the solution.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backdoors {
+    pub size: usize,
+    pub sets: Vec<Vec<PositionalValue<usize>>>,
+}
+
+// places every naked and hidden single, the same way `simple_solve` does before guessing.
+pub(crate) fn solve_singles(board: &Board) -> StrResult<AnalyzedBoard> {
+    let mut analyzed_board = analyze_board(board)?;
+    update_board(&mut analyzed_board)?;
+    place_singles(&mut analyzed_board)?;
+
+    Ok(analyzed_board)
+}
+
+/**
+Finds the smallest sets of cells that, once given their values in the solution,
+let singles alone solve the rest of the board.
+Only the cells singles can't fill are tried, and the number of sets tried
+grows quickly with `max_size`, sizes of 1 and 2 are cheap.
+@returns `None` when no set of up to `max_size` cells is enough.
+*/
+pub fn find_backdoors(board: &Board, max_size: usize) -> StrResult<Option<Backdoors>> {
+    let solutions = find_solutions(board, 2)?;
+
+    let solution = match solutions.len() {
+        0 => return Err("Board has no solution".to_string()),
+        1 => &solutions[0],
+        _ => return Err("Board has more than one solution".to_string()),
+    };
+
+    let size = board.get_size();
+    let stuck = solve_singles(board)?;
+
+    if is_full_board(&stuck) {
+        return Ok(Some(Backdoors {
+            size: 0,
+            sets: vec![vec![]],
+        }));
+    }
+
+    let open: Vec<usize> = stuck
+        .filter(|cell| cell.is_undetermined())
+        .iter()
+        .map(|cell| cell.row * size + cell.col)
+        .collect();
+
+    for amount in 1..=max_size.min(open.len()) {
+        let mut sets: Vec<Vec<PositionalValue<usize>>> = Vec::new();
+
+        for cells in combinations(&open, amount) {
+            let mut hinted = board.clone();
+            let mut set: Vec<PositionalValue<usize>> = Vec::new();
+
+            for index in cells {
+                let (row, col) = (index / size, index % size);
+                let value = *solution.at(row, col).unwrap();
+
+                hinted.set(row, col, value)?;
+                set.push(PositionalValue::new(row, col, value));
+            }
+
+            if is_full_board(&solve_singles(&hinted)?) {
+                sets.push(set);
+            }
+        }
+
+        if !sets.is_empty() {
+            return Ok(Some(Backdoors { size: amount, sets }));
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,372 @@
+use crate::board::Board;
+use crate::solve::Grid;
+use crate::transform::Transform;
+use crate::types::StrResult;
+use std::collections::HashMap;
+
+/**
+Counts the ways to fill a range of rows one row at a time. The rows placed so far
+only matter through the values each col and each square of the current band holds,
+so the count from a row on is kept for every such state.
+*/
+struct Counter {
+    size: usize,
+    square_size: usize,
+    // the values each cell can take, by row, ruling out the givens it sees
+    options: Vec<u64>,
+    start_row: usize,
+    end_row: usize,
+    // from this row on every row is empty
+    blank_from: usize,
+    memo: HashMap<(usize, Vec<u64>), u128>,
+}
+
+fn factorial(n: usize) -> StrResult<u128> {
+    (1..=n as u128).try_fold(1u128, |product, i| {
+        product
+            .checked_mul(i)
+            .ok_or("Completion count is too big for a u128".to_string())
+    })
+}
+
+impl Counter {
+    /**
+    Once the rest of the rows are empty, the order of the cols inside each stack and
+    the order of the stacks don't change the count, so they are sorted away.
+    */
+    fn key(&self, row: usize, cols: &[u64], squares: &[u64]) -> (usize, Vec<u64>) {
+        if row < self.blank_from {
+            return (row, cols.iter().chain(squares.iter()).copied().collect());
+        }
+
+        let mut stacks: Vec<Vec<u64>> = (0..self.square_size)
+            .map(|stack| {
+                let mut stack_cols =
+                    cols[stack * self.square_size..(stack + 1) * self.square_size].to_vec();
+                stack_cols.sort();
+                stack_cols.insert(0, squares[stack]);
+                stack_cols
+            })
+            .collect();
+        stacks.sort();
+
+        (row, stacks.concat())
+    }
+
+    /**
+    Checks the rows from `row` on can still be filled: every cell keeps an option
+    and every row, col and square can still take each value it is missing.
+    */
+    fn feasible(&self, row: usize, cols: &[u64], squares: &[u64]) -> bool {
+        let all = u64::MAX >> (64 - self.size);
+        let band_end = (row / self.square_size + 1) * self.square_size;
+        let mut col_options = vec![0; self.size];
+        let mut square_options = vec![0; self.size];
+
+        for r in row..self.end_row {
+            let mut row_options = 0;
+
+            for (col, &placed) in cols.iter().enumerate() {
+                let stack = col / self.square_size;
+                let mut options = self.options[r * self.size + col] & !placed;
+
+                if r < band_end {
+                    options &= !squares[stack];
+                }
+
+                if options == 0 {
+                    return false;
+                }
+
+                row_options |= options;
+                col_options[col] |= options;
+                square_options[r / self.square_size * self.square_size + stack] |= options;
+            }
+
+            if row_options != all {
+                return false;
+            }
+        }
+
+        // a col only has to take every value when the rows are the whole board
+        let cols_covered = self.start_row > 0
+            || self.end_row < self.size
+            || col_options
+                .iter()
+                .zip(cols)
+                .all(|(&options, &placed)| options | placed == all);
+
+        // squares of the current band already hold some values, the later ones none
+        let squares_covered = (row / self.square_size * self.square_size..self.end_row)
+            .step_by(self.square_size)
+            .flat_map(|band_row| (0..self.square_size).map(move |stack| (band_row, stack)))
+            .all(|(band_row, stack)| {
+                let placed = match band_row < band_end {
+                    true => squares[stack],
+                    false => 0,
+                };
+                square_options[band_row + stack] | placed == all
+            });
+
+        cols_covered && squares_covered
+    }
+
+    fn count_from(&mut self, row: usize, cols: &[u64], squares: &[u64]) -> StrResult<u128> {
+        if row == self.end_row {
+            return Ok(1);
+        }
+
+        if !self.feasible(row, cols, squares) {
+            return Ok(0);
+        }
+
+        let key = self.key(row, cols, squares);
+
+        if let Some(&count) = self.memo.get(&key) {
+            return Ok(count);
+        }
+
+        let count = match row + 1 == self.end_row {
+            true => self.count_last_row(row, cols, squares)?,
+            false => {
+                let mut count: u128 = 0;
+                let mut cols = cols.to_vec();
+                let mut squares = squares.to_vec();
+                self.fill_row(row, 0, 0, &mut cols, &mut squares, &mut count)?;
+                count
+            }
+        };
+
+        self.memo.insert(key, count);
+        Ok(count)
+    }
+
+    /**
+    In the last row of a band every square takes the values it is still missing, and those
+    never repeat across squares. So the row is counted as the product of the ways to match
+    each stack's cols with the values missing from its square.
+    */
+    fn count_last_row(&self, row: usize, cols: &[u64], squares: &[u64]) -> StrResult<u128> {
+        let mut count: u128 = 1;
+
+        for (stack, &placed) in squares.iter().enumerate() {
+            let options: Vec<u64> = (stack * self.square_size..(stack + 1) * self.square_size)
+                .map(|col| self.options[row * self.size + col] & !placed & !cols[col])
+                .collect();
+
+            count = count
+                .checked_mul(matchings(&options, 0))
+                .ok_or("Completion count is too big for a u128".to_string())?;
+        }
+
+        Ok(count)
+    }
+
+    // places the cells of the row from `col` on, counting the rows below every full row
+    fn fill_row(
+        &mut self,
+        row: usize,
+        col: usize,
+        used: u64,
+        cols: &mut [u64],
+        squares: &mut [u64],
+        count: &mut u128,
+    ) -> StrResult<()> {
+        if col == self.size {
+            let below = match (row + 1) % self.square_size {
+                0 => self.count_from(row + 1, cols, &vec![0; self.square_size])?,
+                _ => self.count_from(row + 1, cols, squares)?,
+            };
+
+            *count = count
+                .checked_add(below)
+                .ok_or("Completion count is too big for a u128".to_string())?;
+            return Ok(());
+        }
+
+        let stack = col / self.square_size;
+        let taken = used | cols[col] | squares[stack];
+
+        let mut free = self.options[row * self.size + col] & !taken;
+
+        while free != 0 {
+            let bit = free & free.wrapping_neg();
+            free &= !bit;
+            cols[col] |= bit;
+            squares[stack] |= bit;
+            self.fill_row(row, col + 1, used | bit, cols, squares, count)?;
+            cols[col] &= !bit;
+            squares[stack] &= !bit;
+        }
+
+        Ok(())
+    }
+}
+
+// the ways to give each col one of its options, with no option used twice
+fn matchings(options: &[u64], used: u64) -> u128 {
+    match options.split_first() {
+        None => 1,
+        Some((first, rest)) => {
+            let mut free = first & !used;
+            let mut count = 0;
+
+            while free != 0 {
+                let bit = free & free.wrapping_neg();
+                count += matchings(rest, used | bit);
+                free &= !bit;
+            }
+
+            count
+        }
+    }
+}
+
+// counts the fillings of the rows `start_row..end_row`, the board has no givens outside them
+fn count_rows(board: &Board, start_row: usize, end_row: usize) -> StrResult<u128> {
+    let size = board.get_size();
+
+    let grid = match Grid::from_board(board)? {
+        Some(grid) => grid,
+        None => return Ok(0),
+    };
+
+    let options = grid
+        .cells()
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| match value {
+            0 => grid.options(index),
+            given => 1 << (given - 1),
+        })
+        .collect();
+
+    let blank_from = (start_row..end_row)
+        .rev()
+        .find(|&row| board.get_row(row).unwrap().iter().any(|&v| v != 0))
+        .map_or(start_row, |row| row + 1);
+
+    let mut counter = Counter {
+        size,
+        square_size: board.get_square_size(),
+        options,
+        start_row,
+        end_row,
+        blank_from,
+        memo: HashMap::new(),
+    };
+
+    let cols = vec![0; size];
+    let squares = vec![0; counter.square_size];
+
+    // with no givens at all the values can be relabeled, so the first row is fixed to 1..=size
+    if blank_from == start_row {
+        for col in 0..size {
+            counter.options[start_row * size + col] = 1 << col;
+        }
+        counter.blank_from = start_row + 1;
+
+        let count = counter.count_from(start_row, &cols, &squares)?;
+        return count
+            .checked_mul(factorial(size)?)
+            .ok_or("Completion count is too big for a u128".to_string());
+    }
+
+    counter.count_from(start_row, &cols, &squares)
+}
+
+/**
+Counts every valid completion of the board exactly, also when there are far too many to list.
+Fast for small boards and for 9x9 boards with about 20 givens or more,
+but not for nearly empty 9x9 boards, whose counts run into the sextillions.
+@returns an error when the count doesn't fit a u128.
+*/
+pub fn count_completions(board: &Board) -> StrResult<u128> {
+    let size = board.get_size();
+    let arranged = [false, true]
+        .into_iter()
+        .map(|transpose| constrained_first(board, transpose))
+        .collect::<StrResult<Vec<_>>>()?
+        .into_iter()
+        .max_by_key(|(first_band, _)| *first_band)
+        .unwrap()
+        .1;
+
+    count_rows(&arranged, 0, size)
+}
+
+/**
+Moves the bands with the most givens to the top, and the rows with the most givens to
+the top of their band, as the givens of the first rows cut the most fillings.
+@param transpose whether the cols are ordered and become the rows instead.
+@returns the moved board together with the givens in its first band.
+*/
+fn constrained_first(board: &Board, transpose: bool) -> StrResult<(usize, Board)> {
+    let size = board.get_size();
+    let square_size = board.get_square_size();
+
+    let givens: Vec<usize> = (0..size)
+        .map(|line| {
+            (0..size)
+                .filter(|&i| match transpose {
+                    true => board.at(i, line) != Some(&0),
+                    false => board.at(line, i) != Some(&0),
+                })
+                .count()
+        })
+        .collect();
+
+    let band_givens = |band: usize| -> usize {
+        givens[band * square_size..(band + 1) * square_size]
+            .iter()
+            .sum()
+    };
+
+    let mut bands: Vec<usize> = (0..square_size).collect();
+    bands.sort_by_key(|&band| std::cmp::Reverse(band_givens(band)));
+
+    // the rows in their new order
+    let order: Vec<usize> = bands
+        .iter()
+        .flat_map(|&band| {
+            let mut rows: Vec<usize> = (band * square_size..(band + 1) * square_size).collect();
+            rows.sort_by_key(|&row| std::cmp::Reverse(givens[row]));
+            rows
+        })
+        .collect();
+
+    let mut rows = vec![0; size];
+    for (to, &from) in order.iter().enumerate() {
+        rows[from] = to;
+    }
+
+    let moved = Transform::from_parts(transpose, rows, (0..size).collect(), (1..=size).collect())?
+        .apply(board)?;
+
+    Ok((band_givens(bands[0]), moved))
+}
+
+/**
+Counts the ways to fill the rows of a single band, taking only the givens inside it into account.
+@param band the index of the band, 0 is the top one.
+*/
+pub fn count_band_completions(board: &Board, band: usize) -> StrResult<u128> {
+    let square_size = board.get_square_size();
+
+    if band >= square_size {
+        return Err(format!(
+            "Band {band} is out of a board with {square_size} bands"
+        ));
+    }
+
+    let rows = band * square_size..(band + 1) * square_size;
+    let mut band_only = Board::new(board.get_size())?;
+
+    for row in rows.clone() {
+        for col in 0..board.get_size() {
+            band_only.set(row, col, *board.at(row, col).unwrap())?;
+        }
+    }
+
+    count_rows(&band_only, rows.start, rows.end)
+}
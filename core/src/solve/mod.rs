@@ -1,6 +1,16 @@
+pub use backbone::*;
+pub use backdoor::*;
+pub use completions::*;
+pub use search::*;
 pub use solve::*;
+pub use unavoidable::*;
 
 #[cfg(test)]
 mod test;
 
+mod backbone;
+mod backdoor;
+mod completions;
+mod search;
 mod solve;
+mod unavoidable;
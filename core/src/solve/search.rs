@@ -0,0 +1,413 @@
+use crate::board::{Board, BoardData};
+use crate::types::StrResult;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+// largest board that fits the u64 option masks
+const MAX_SIZE: usize = 64;
+
+/**
+A compact board for exhaustive searches, where each row, col and square keeps
+a bit mask of the values it already holds.
+*/
+#[derive(Debug, Clone)]
+pub(crate) struct Grid {
+    size: usize,
+    square_size: usize,
+    cells: Vec<usize>,
+    rows: Vec<u64>,
+    cols: Vec<u64>,
+    squares: Vec<u64>,
+    // cell indices of every row, col and square
+    houses: Vec<Vec<usize>>,
+}
+
+impl Grid {
+    /**
+    @returns `None` when the board holds the same value twice in a row, col or square.
+    */
+    pub(crate) fn from_board(board: &Board) -> StrResult<Option<Grid>> {
+        let size = board.get_size();
+
+        if size > MAX_SIZE {
+            return Err(format!("Board of size {size} is too big to search"));
+        }
+
+        let mut grid = Grid {
+            size,
+            square_size: board.get_square_size(),
+            cells: vec![0; size * size],
+            rows: vec![0; size],
+            cols: vec![0; size],
+            squares: vec![0; size],
+            houses: Vec::new(),
+        };
+
+        let square_size = grid.square_size;
+        for i in 0..size {
+            let (start_row, start_col) =
+                (i / square_size * square_size, i % square_size * square_size);
+            grid.houses
+                .push((0..size).map(|col| i * size + col).collect());
+            grid.houses
+                .push((0..size).map(|row| row * size + i).collect());
+            grid.houses.push(
+                (0..size)
+                    .map(|j| (start_row + j / square_size) * size + start_col + j % square_size)
+                    .collect(),
+            );
+        }
+
+        for cell in board.get_flat() {
+            let value = *cell.value;
+
+            if value > size {
+                return Err(format!(
+                    "Value of {value} in ({},{}) is not valid: Too big",
+                    cell.row, cell.col
+                ));
+            }
+
+            if value == 0 {
+                continue;
+            }
+
+            let index = cell.row * size + cell.col;
+
+            if grid.options(index) & (1 << (value - 1)) == 0 {
+                return Ok(None);
+            }
+
+            grid.set(index, value);
+        }
+
+        Ok(Some(grid))
+    }
+
+    fn square_index(&self, index: usize) -> usize {
+        let row = index / self.size;
+        let col = index % self.size;
+        row / self.square_size * self.square_size + col / self.square_size
+    }
+
+    fn full_mask(&self) -> u64 {
+        if self.size == MAX_SIZE {
+            u64::MAX
+        } else {
+            (1 << self.size) - 1
+        }
+    }
+
+    // the values of every cell by row, 0 for the empty ones
+    pub(crate) fn cells(&self) -> &[usize] {
+        &self.cells
+    }
+
+    pub(crate) fn options(&self, index: usize) -> u64 {
+        let row = index / self.size;
+        let col = index % self.size;
+        let used = self.rows[row] | self.cols[col] | self.squares[self.square_index(index)];
+        self.full_mask() & !used
+    }
+
+    pub(crate) fn set(&mut self, index: usize, value: usize) {
+        let bit = 1 << (value - 1);
+        let square = self.square_index(index);
+        self.cells[index] = value;
+        self.rows[index / self.size] |= bit;
+        self.cols[index % self.size] |= bit;
+        self.squares[square] |= bit;
+    }
+
+    pub(crate) fn unset(&mut self, index: usize) {
+        let bit = !(1 << (self.cells[index] - 1));
+        let square = self.square_index(index);
+        self.cells[index] = 0;
+        self.rows[index / self.size] &= bit;
+        self.cols[index % self.size] &= bit;
+        self.squares[square] &= bit;
+    }
+
+    /**
+    Places every naked and hidden single, recording the placed cells in `trail`.
+    @returns false when a cell or a house runs out of options.
+    */
+    fn propagate(&mut self, trail: &mut Vec<usize>) -> bool {
+        let full_mask = self.full_mask();
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for index in 0..self.cells.len() {
+                if self.cells[index] != 0 {
+                    continue;
+                }
+
+                let options = self.options(index);
+
+                if options == 0 {
+                    return false;
+                }
+
+                if options.count_ones() == 1 {
+                    self.set(index, options.trailing_zeros() as usize + 1);
+                    trail.push(index);
+                    changed = true;
+                }
+            }
+
+            for h in 0..self.houses.len() {
+                let (mut placed, mut once, mut more) = (0u64, 0u64, 0u64);
+
+                for &index in self.houses[h].iter() {
+                    if self.cells[index] != 0 {
+                        placed |= 1 << (self.cells[index] - 1);
+                    } else {
+                        let options = self.options(index);
+                        more |= once & options;
+                        once |= options;
+                    }
+                }
+
+                let missing = full_mask & !placed;
+
+                if once & missing != missing {
+                    return false;
+                }
+
+                let singles = once & !more & missing;
+
+                if singles == 0 {
+                    continue;
+                }
+
+                // the other singles of the house are picked up on the next round
+                let bit = singles & singles.wrapping_neg();
+                let index = *self.houses[h]
+                    .iter()
+                    .find(|&&i| self.cells[i] == 0 && self.options(i) & bit != 0)
+                    .unwrap();
+                self.set(index, bit.trailing_zeros() as usize + 1);
+                trail.push(index);
+                changed = true;
+            }
+        }
+
+        true
+    }
+
+    // the empty cell with the least options, or None when the grid is full.
+    fn next_cell(&self) -> Option<(usize, u64)> {
+        let mut best: Option<(usize, u64)> = None;
+
+        for index in (0..self.cells.len()).filter(|&i| self.cells[i] == 0) {
+            let options = self.options(index);
+            let count = options.count_ones();
+
+            if best.is_none_or(|(_, o)| count < o.count_ones()) {
+                best = Some((index, options));
+
+                if count <= 1 {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+
+    /**
+    Visits every solution of the grid until `visit` returns false.
+    @returns false when the search was stopped by `visit`.
+    */
+    pub(crate) fn search<F>(&mut self, visit: &mut F) -> bool
+    where
+        F: FnMut(&Grid) -> bool,
+    {
+        let mut nodes = usize::MAX;
+        self.search_within(&mut nodes, visit)
+    }
+
+    /**
+    Like `search`, but also stops once `nodes` runs out, taking one for every node of the search:
+    the grid it starts from and each value it guesses.
+    */
+    pub(crate) fn search_within<F>(&mut self, nodes: &mut usize, visit: &mut F) -> bool
+    where
+        F: FnMut(&Grid) -> bool,
+    {
+        if *nodes == 0 {
+            return false;
+        }
+        *nodes -= 1;
+
+        let mut trail: Vec<usize> = Vec::new();
+        let mut keep_going = true;
+
+        if self.propagate(&mut trail) {
+            match self.next_cell() {
+                None => keep_going = visit(self),
+                Some((index, options)) => {
+                    for value in (1..=self.size).filter(|v| options & (1 << (v - 1)) != 0) {
+                        self.set(index, value);
+                        keep_going = self.search_within(nodes, visit);
+                        self.unset(index);
+
+                        if !keep_going {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        for index in trail.into_iter().rev() {
+            self.unset(index);
+        }
+
+        keep_going
+    }
+
+    /**
+    Fills the grid with the first solution found, trying the values of each cell
+    in the order `order` leaves them in.
+    @returns false, leaving the grid as it was, when there is no solution.
+    */
+    pub(crate) fn fill_with<F>(&mut self, order: &mut F) -> bool
+    where
+        F: FnMut(&mut [usize]),
+    {
+        let mut trail: Vec<usize> = Vec::new();
+
+        if self.propagate(&mut trail) {
+            match self.next_cell() {
+                None => return true,
+                Some((index, options)) => {
+                    let mut values: Vec<usize> = (1..=self.size)
+                        .filter(|v| options & (1 << (v - 1)) != 0)
+                        .collect();
+                    order(&mut values);
+
+                    for value in values {
+                        self.set(index, value);
+
+                        if self.fill_with(order) {
+                            return true;
+                        }
+
+                        self.unset(index);
+                    }
+                }
+            }
+        }
+
+        for index in trail.into_iter().rev() {
+            self.unset(index);
+        }
+
+        false
+    }
+
+    pub(crate) fn to_board(&self) -> StrResult<Board> {
+        let data: BoardData = self
+            .cells
+            .chunks(self.size)
+            .map(|row| row.to_vec())
+            .collect();
+
+        Board::from(&data)
+    }
+}
+
+/**
+Counts the solutions of the board, stopping once `limit` solutions were found.
+*/
+pub fn count_solutions(board: &Board, limit: usize) -> StrResult<usize> {
+    let mut grid = match Grid::from_board(board)? {
+        Some(grid) => grid,
+        None => return Ok(0),
+    };
+    let mut count: usize = 0;
+
+    if limit > 0 {
+        grid.search(&mut |_| {
+            count += 1;
+            count < limit
+        });
+    }
+
+    Ok(count)
+}
+
+/**
+@returns up to `limit` solutions of the board.
+*/
+pub fn find_solutions(board: &Board, limit: usize) -> StrResult<Vec<Board>> {
+    let mut grid = match Grid::from_board(board)? {
+        Some(grid) => grid,
+        None => return Ok(vec![]),
+    };
+    let mut solutions: Vec<StrResult<Board>> = Vec::new();
+
+    if limit > 0 {
+        grid.search(&mut |solved| {
+            solutions.push(solved.to_board());
+            solutions.len() < limit
+        });
+    }
+
+    solutions.into_iter().collect()
+}
+
+// the first solution found by `Grid::fill_with`, or `None` when there is none.
+pub(crate) fn completion_with<F>(board: &Board, order: &mut F) -> StrResult<Option<Board>>
+where
+    F: FnMut(&mut [usize]),
+{
+    let mut grid = match Grid::from_board(board)? {
+        Some(grid) => grid,
+        None => return Ok(None),
+    };
+
+    if !grid.fill_with(order) {
+        return Ok(None);
+    }
+
+    grid.to_board().map(Some)
+}
+
+/**
+@returns a random solution of the board, or `None` when it has none.
+*/
+pub fn random_completion<R: Rng + ?Sized>(board: &Board, rng: &mut R) -> StrResult<Option<Board>> {
+    completion_with(board, &mut |values| values.shuffle(rng))
+}
+
+pub fn has_unique_solution(board: &Board) -> StrResult<bool> {
+    Ok(count_solutions(board, 2)? == 1)
+}
+
+/**
+Like `has_unique_solution`, but gives up after visiting `max_nodes` nodes of the search.
+@returns `None` when the search gave up before deciding.
+*/
+pub(crate) fn unique_within(board: &Board, max_nodes: usize) -> StrResult<Option<bool>> {
+    let mut grid = match Grid::from_board(board)? {
+        Some(grid) => grid,
+        None => return Ok(Some(false)),
+    };
+    let mut nodes = max_nodes;
+    let mut count: usize = 0;
+
+    grid.search_within(&mut nodes, &mut |_| {
+        count += 1;
+        count < 2
+    });
+
+    Ok(match (count, nodes) {
+        (2, _) => Some(false),
+        (_, 0) => None,
+        (count, _) => Some(count == 1),
+    })
+}
@@ -74,21 +74,15 @@ fn guess_cell(
     })
 }
 
-fn solve_analyzed(
-    board: &AnalyzedBoard,
-    rng_thread: &mut ThreadRng,
-    rec: usize,
-) -> StrResult<Board> {
-    println!("[^] Entering limit {rec}");
-
-    let mut analyzed_board = board.clone();
-    let mut positions: Vec<InferredPosition> = infer_all(&analyzed_board)?;
+/**
+Places naked and hidden singles until there are none left.
+*/
+pub(crate) fn place_singles(analyzed_board: &mut AnalyzedBoard) -> StrResult<()> {
+    let mut positions: Vec<InferredPosition> = infer_all(analyzed_board)?;
     let mut changed_positions: Vec<(usize, usize)> = Vec::new();
 
-    println!("{}", &analyzed_board);
-
-    // updating the board1 until there is nothing to updates
-    while positions.len() > 0 {
+    // updating the board until there is nothing to update
+    while !positions.is_empty() {
         changed_positions.clear();
 
         for pos in positions.iter() {
@@ -96,13 +90,29 @@ fn solve_analyzed(
             changed_positions.push((pos.row, pos.col));
         }
 
-        let updated_positions = update_positions(&mut analyzed_board, &changed_positions)?;
+        let updated_positions = update_positions(analyzed_board, &changed_positions)?;
 
         changed_positions.extend(&updated_positions);
 
-        positions = infer_positions(&analyzed_board, &changed_positions)?;
+        positions = infer_positions(analyzed_board, &changed_positions)?;
     }
 
+    Ok(())
+}
+
+fn solve_analyzed(
+    board: &AnalyzedBoard,
+    rng_thread: &mut ThreadRng,
+    rec: usize,
+) -> StrResult<Board> {
+    println!("[^] Entering limit {rec}");
+
+    let mut analyzed_board = board.clone();
+
+    println!("{}", &analyzed_board);
+
+    place_singles(&mut analyzed_board)?;
+
     if is_full_board(&analyzed_board) {
         return to_board(&analyzed_board);
     }
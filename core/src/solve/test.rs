@@ -1,5 +1,10 @@
+use crate::analyze::is_full_board;
 use crate::board::Board;
-use crate::solve::simple_solve;
+use crate::solve::{
+    count_band_completions, count_completions, count_solutions, find_backbone, find_backdoors,
+    find_solutions, has_unique_solution, simple_solve, solve_singles, unavoidable_sets,
+};
+use crate::test_utils::{board_from_str, EASY_PUZZLE, EASY_SOLUTION};
 use crate::types::StrResult;
 
 #[test]
@@ -45,3 +50,209 @@ fn should_solve_empty_board() -> StrResult<()> {
     assert_eq!(solved.is_full(), true);
     Ok(())
 }
+
+#[test]
+fn should_count_unique_solution() -> StrResult<()> {
+    let board = board_from_str(EASY_PUZZLE);
+
+    assert_eq!(count_solutions(&board, 10)?, 1);
+    assert!(has_unique_solution(&board)?);
+    assert_eq!(
+        find_solutions(&board, 10)?[0].get_rows(),
+        board_from_str(EASY_SOLUTION).get_rows()
+    );
+    Ok(())
+}
+
+#[test]
+fn should_count_all_4x4_grids() -> StrResult<()> {
+    let board = Board::new(4)?;
+
+    assert_eq!(count_solutions(&board, 1000)?, 288);
+    assert_eq!(count_solutions(&board, 5)?, 5);
+    Ok(())
+}
+
+#[test]
+fn no_solutions_for_conflicting_givens() -> StrResult<()> {
+    let mut board = Board::new(9)?;
+    board.set(0, 0, 1)?;
+    board.set(8, 0, 1)?;
+
+    assert_eq!(count_solutions(&board, 1)?, 0);
+    assert!(find_solutions(&board, 1)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn should_find_unavoidable_sets() -> StrResult<()> {
+    let solution = board_from_str(EASY_SOLUTION);
+    let sets = unavoidable_sets(&solution, 6)?;
+
+    assert!(!sets.is_empty());
+    for set in sets.iter() {
+        assert!(set.len() >= 4 && set.len() <= 6);
+
+        // blanking a set leaves another solution
+        let mut blanked = solution.clone();
+        for &(row, col) in set.iter() {
+            blanked.set(row, col, 0)?;
+        }
+        assert!(count_solutions(&blanked, 2)? > 1);
+    }
+
+    // the givens of a unique puzzle hit every set
+    let puzzle = board_from_str(EASY_PUZZLE);
+    for set in sets.iter() {
+        assert!(set
+            .iter()
+            .any(|&(row, col)| puzzle.at(row, col) != Some(&0)));
+    }
+    Ok(())
+}
+
+#[test]
+fn unavoidable_sets_of_4x4() -> StrResult<()> {
+    let solution = board_from_str("1234341221434321");
+    let sets = unavoidable_sets(&solution, 4)?;
+
+    // r1c1,r1c3,r2c1,r2c3 hold 1,3,3,1 and can swap
+    assert!(sets.contains(&vec![(0, 0), (0, 2), (1, 0), (1, 2)]));
+    assert!(unavoidable_sets(&board_from_str("1234341221434320"), 4).is_err());
+    Ok(())
+}
+
+#[test]
+fn should_find_backdoors() -> StrResult<()> {
+    assert_eq!(
+        find_backdoors(&board_from_str(EASY_PUZZLE), 2)?.map(|b| b.size),
+        Some(0)
+    );
+
+    // needs locked candidates, a single hint is enough
+    let board = board_from_str(
+        "900008360302000000050000009610000040205800000000005001003057900020600004004300005",
+    );
+    let backdoors = find_backdoors(&board, 2)?.expect("Backdoor not found");
+
+    assert_eq!(backdoors.size, 1);
+    assert_eq!(backdoors.sets.len(), 9);
+
+    for set in backdoors.sets.iter() {
+        let mut hinted = board.clone();
+        hinted.set(set[0].row(), set[0].col(), *set[0].value())?;
+        assert!(is_full_board(&solve_singles(&hinted)?));
+    }
+    Ok(())
+}
+
+#[test]
+fn no_backdoors_within_size() -> StrResult<()> {
+    let board = board_from_str(
+        "800000000003600000070090200050007000000045700000100030001000068008500010090000400",
+    );
+
+    assert_eq!(find_backdoors(&board, 1)?, None);
+    assert!(find_backdoors(&Board::new(4)?, 1).is_err());
+    Ok(())
+}
+
+#[test]
+fn should_find_backbone() -> StrResult<()> {
+    let solution = board_from_str(EASY_SOLUTION);
+    let mut board = board_from_str(EASY_PUZZLE);
+    for (row, col) in [(0, 0), (0, 1), (1, 0), (4, 0)] {
+        board.set(row, col, 0)?;
+    }
+
+    let backbone = find_backbone(&board)?;
+    let solutions = find_solutions(&board, 1000)?;
+
+    assert!(solutions.len() > 1);
+    for row in 0..9 {
+        for col in 0..9 {
+            let mut values: Vec<usize> =
+                solutions.iter().map(|s| *s.at(row, col).unwrap()).collect();
+            values.sort();
+            values.dedup();
+
+            assert_eq!(backbone.possible.at(row, col), Some(&values));
+        }
+    }
+
+    assert!(!backbone.ambiguous().is_empty());
+    for fixed in backbone.fixed.iter() {
+        assert_eq!(solution.at(fixed.row(), fixed.col()), Some(fixed.value()));
+    }
+    Ok(())
+}
+
+#[test]
+fn backbone_of_unique_board_is_its_solution() -> StrResult<()> {
+    let backbone = find_backbone(&board_from_str(EASY_PUZZLE))?;
+
+    assert_eq!(backbone.fixed.len(), 81 - 30);
+    assert!(backbone.ambiguous().is_empty());
+
+    let empty = find_backbone(&Board::new(4)?)?;
+    assert!(empty.fixed.is_empty());
+    assert_eq!(empty.possible.at(0, 0), Some(&vec![1, 2, 3, 4]));
+    Ok(())
+}
+
+#[test]
+fn should_count_completions_exactly() -> StrResult<()> {
+    assert_eq!(count_completions(&Board::new(4)?)?, 288);
+    assert_eq!(count_completions(&board_from_str(EASY_PUZZLE))?, 1);
+    assert_eq!(count_completions(&board_from_str(EASY_SOLUTION))?, 1);
+
+    let mut board = board_from_str(EASY_PUZZLE);
+    board.set(0, 0, 1)?;
+    assert_eq!(count_completions(&board)?, 0);
+
+    let mut board = board_from_str(EASY_SOLUTION);
+    for row in 0..9 {
+        for col in 0..9 {
+            if (row * 9 + col) % 2 == 0 {
+                board.set(row, col, 0)?;
+            }
+        }
+    }
+    assert_eq!(
+        count_completions(&board)?,
+        count_solutions(&board, usize::MAX)? as u128
+    );
+
+    let mut board = board_from_str(EASY_PUZZLE);
+    for (row, col) in [(0, 0), (0, 1), (1, 0), (4, 0), (8, 8), (6, 7)] {
+        board.set(row, col, 0)?;
+    }
+    assert_eq!(
+        count_completions(&board)?,
+        count_solutions(&board, usize::MAX)? as u128
+    );
+    Ok(())
+}
+
+#[test]
+fn should_count_band_completions() -> StrResult<()> {
+    // 9! * 56 * 6^6 ways to fill the top band of an empty grid
+    assert_eq!(count_band_completions(&Board::new(9)?, 0)?, 948_109_639_680);
+    assert_eq!(count_band_completions(&Board::new(4)?, 1)?, 96);
+
+    // the givens below the band don't matter
+    let solution = board_from_str(EASY_SOLUTION);
+    let mut band = solution.clone();
+    for row in 0..3 {
+        for col in 0..9 {
+            band.set(row, col, 0)?;
+        }
+    }
+    assert_eq!(
+        count_band_completions(&band, 0)?,
+        count_band_completions(&Board::new(9)?, 0)?
+    );
+    assert_eq!(count_band_completions(&solution, 2)?, 1);
+    assert!(count_band_completions(&solution, 3).is_err());
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use crate::board::Board;
+use crate::combinatorics::combinations;
+use crate::solve::Grid;
+use crate::types::StrResult;
+use std::collections::BTreeSet;
+
+/**
+Finds the minimal unavoidable sets of a full board with at most `max_size` cells,
+sorted by size. Their values can be rearranged into another valid board,
+so every puzzle of this solution must give at least one cell of each.
+Such a set uses at most `max_size / 2` digits, so the board is searched once with
+every group of that many digits blanked, which gets slow above a size of about 10.
+@returns the cells of each set, by row and col.
+*/
+pub fn unavoidable_sets(board: &Board, max_size: usize) -> StrResult<Vec<Vec<(usize, usize)>>> {
+    if !board.is_full() {
+        return Err("Unavoidable sets need a full board".to_string());
+    }
+
+    let size = board.get_size();
+
+    if Grid::from_board(board)?.is_none() {
+        return Err("Board is not a valid solution".to_string());
+    }
+
+    let values: Vec<usize> = board.get_rows_flat().into_iter().copied().collect();
+    let digits: Vec<usize> = (1..=size).collect();
+    let mut found: BTreeSet<Vec<usize>> = BTreeSet::new();
+
+    for amount in 2..=(max_size / 2).min(size) {
+        for group in combinations(&digits, amount) {
+            let mut blanked = board.clone();
+
+            for (index, value) in values.iter().enumerate() {
+                if group.contains(value) {
+                    blanked.set(index / size, index % size, 0)?;
+                }
+            }
+
+            let mut grid = Grid::from_board(&blanked)?.unwrap();
+            grid.search(&mut |solved| {
+                let differing: Vec<usize> = (0..values.len())
+                    .filter(|&i| solved.cells()[i] != values[i])
+                    .collect();
+
+                if !differing.is_empty() && differing.len() <= max_size {
+                    found.insert(differing);
+                }
+
+                true
+            });
+        }
+    }
+
+    let mut sets: Vec<Vec<usize>> = found.into_iter().collect();
+    sets.sort_by_key(|set| set.len());
+
+    // a set with a smaller one inside it is not minimal
+    let mut minimal: Vec<Vec<usize>> = Vec::new();
+    for set in sets {
+        if !minimal
+            .iter()
+            .any(|smaller| smaller.iter().all(|i| set.contains(i)))
+        {
+            minimal.push(set);
+        }
+    }
+
+    Ok(minimal
+        .into_iter()
+        .map(|set| set.into_iter().map(|i| (i / size, i % size)).collect())
+        .collect())
+}
@@ -0,0 +1,14 @@
+pub use notation::*;
+pub use rating::*;
+pub use steps::*;
+pub use techniques::*;
+pub use trace::*;
+
+mod notation;
+mod rating;
+mod steps;
+mod techniques;
+mod trace;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,320 @@
+use crate::explain::{cell_name, cells_names, digits_names, house_short_name};
+use crate::steps::{House, SolveStep, Technique};
+use crate::types::{PositionalValue, StrResult};
+
+// boards up to 9x9 use HoDoKu's compact form for houses of one kind, "r26" for rows 2 and 6.
+fn join_houses(houses: &[House], size: usize) -> String {
+    let names: Vec<String> = houses.iter().map(house_short_name).collect();
+    let same_kind = houses
+        .windows(2)
+        .all(|pair| std::mem::discriminant(&pair[0]) == std::mem::discriminant(&pair[1]));
+
+    match names.first() {
+        Some(first) if size <= 9 && same_kind => format!(
+            "{}{}",
+            &first[..1],
+            names.iter().map(|name| &name[1..]).collect::<String>()
+        ),
+        _ => names.join(","),
+    }
+}
+
+/**
+Formats the step in HoDoKu's notation, e.g. "Naked Pair: 3,7 in r1c2,r1c5 => r1c8<>3".
+@param size the size of the board the step refers to.
+*/
+pub fn format_step(step: &SolveStep, size: usize) -> String {
+    let name = step.technique.name();
+
+    if step.technique.is_single() {
+        let placements: Vec<String> = step
+            .placements
+            .iter()
+            .map(|p| format!("{}={}", cell_name(p.row, p.col), p.value))
+            .collect();
+        return format!("{name}: {}", placements.join(", "));
+    }
+
+    let pattern = match step.technique {
+        Technique::LockedCandidatesPointing | Technique::LockedCandidatesClaiming => format!(
+            "{} in {}",
+            digits_names(&step.digits).join(","),
+            join_houses(&step.base, size)
+        ),
+        Technique::XWing | Technique::Swordfish => format!(
+            "{} {} {}",
+            digits_names(&step.digits).join(","),
+            join_houses(&step.base, size),
+            join_houses(&step.cover, size)
+        ),
+        _ => format!(
+            "{} in {}",
+            digits_names(&step.digits).join(","),
+            cells_names(&step.cells).join(",")
+        ),
+    };
+
+    let eliminations: Vec<String> = step
+        .eliminations
+        .iter()
+        .map(|e| format!("{}<>{}", cell_name(e.row, e.col), e.value))
+        .collect();
+
+    format!("{name}: {pattern} => {}", eliminations.join(", "))
+}
+
+pub fn format_steps(steps: &[SolveStep], size: usize) -> String {
+    steps
+        .iter()
+        .map(|step| format_step(step, size))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_technique(name: &str) -> StrResult<Technique> {
+    let name = name.trim();
+
+    let alias = match name {
+        "Locked Candidates Type 1" | "Pointing" => Some(Technique::LockedCandidatesPointing),
+        "Locked Candidates Type 2" | "Claiming" => Some(Technique::LockedCandidatesClaiming),
+        _ => None,
+    };
+
+    alias
+        .or_else(|| Technique::ALL.into_iter().find(|t| t.name() == name))
+        .ok_or(format!("Unsupported technique `{name}`"))
+}
+
+// boards up to 9x9 use HoDoKu's compact form, where "r12" means rows 1 and 2.
+fn parse_indices(text: &str, size: usize) -> StrResult<Vec<usize>> {
+    let numbers: Vec<usize> = if size <= 9 {
+        text.chars()
+            .map(|c| c.to_digit(10).map(|d| d as usize))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(format!("Invalid number `{text}`"))?
+    } else {
+        vec![text
+            .parse()
+            .map_err(|_| format!("Invalid number `{text}`"))?]
+    };
+
+    if numbers.is_empty() || numbers.iter().any(|&n| n == 0 || n > size) {
+        return Err(format!("Invalid number `{text}` for board of size {size}"));
+    }
+
+    Ok(numbers)
+}
+
+fn parse_digits(text: &str, size: usize) -> StrResult<Vec<usize>> {
+    let mut digits: Vec<usize> = Vec::new();
+
+    for part in text.split(',') {
+        digits.extend(parse_indices(part.trim(), size)?);
+    }
+
+    Ok(digits)
+}
+
+fn parse_cells(text: &str, size: usize) -> StrResult<Vec<(usize, usize)>> {
+    let mut cells: Vec<(usize, usize)> = Vec::new();
+
+    for part in text.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (rows, cols) = part
+            .strip_prefix('r')
+            .and_then(|rest| rest.split_once('c'))
+            .ok_or(format!("Invalid cell `{part}`"))?;
+
+        for row in parse_indices(rows, size)? {
+            for col in parse_indices(cols, size)? {
+                cells.push((row - 1, col - 1));
+            }
+        }
+    }
+
+    Ok(cells)
+}
+
+fn parse_houses(text: &str, size: usize) -> StrResult<Vec<House>> {
+    let mut houses: Vec<House> = Vec::new();
+
+    for part in text.split(',').map(str::trim) {
+        let mut chars = part.chars();
+        let kind = chars.next().ok_or(format!("Invalid house `{part}`"))?;
+        let indices = parse_indices(chars.as_str(), size)?;
+
+        for i in indices {
+            houses.push(match kind {
+                'r' => House::Row(i - 1),
+                'c' => House::Col(i - 1),
+                'b' => House::Square(i - 1),
+                _ => return Err(format!("Invalid house `{part}`")),
+            });
+        }
+    }
+
+    Ok(houses)
+}
+
+// placements and eliminations of a step
+type Effects = (Vec<PositionalValue<usize>>, Vec<PositionalValue<usize>>);
+
+// parses "r1c8<>3, r1c89<>7" and "r4c6=7" lists
+fn parse_effects(text: &str, size: usize) -> StrResult<Effects> {
+    let mut placements: Vec<PositionalValue<usize>> = Vec::new();
+    let mut eliminations: Vec<PositionalValue<usize>> = Vec::new();
+    let mut pending: Vec<&str> = Vec::new();
+
+    for item in text.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+        let (cells, digits, is_elimination) = if let Some((cells, digits)) = item.split_once("<>") {
+            (cells, digits, true)
+        } else if let Some((cells, digits)) = item.split_once('=') {
+            (cells, digits, false)
+        } else {
+            // a cell without an operator shares the operator of the next item.
+            pending.push(item);
+            continue;
+        };
+
+        pending.push(cells);
+        let cells = parse_cells(&pending.join(","), size)?;
+        let digits = parse_indices(digits.trim(), size)?;
+        pending.clear();
+
+        for &(row, col) in cells.iter() {
+            for &value in digits.iter() {
+                let position = PositionalValue { row, col, value };
+                if is_elimination {
+                    eliminations.push(position);
+                } else {
+                    placements.push(position);
+                }
+            }
+        }
+    }
+
+    if !pending.is_empty() {
+        return Err(format!("Missing operator after `{}`", pending.join(",")));
+    }
+
+    Ok((placements, eliminations))
+}
+
+// finds a house containing all the given cells, preferring rows, then cols, then squares.
+fn common_houses(cells: &[(usize, usize)], size: usize) -> Vec<House> {
+    let (row, col) = match cells.first() {
+        Some(&cell) => cell,
+        None => return vec![],
+    };
+
+    [
+        House::Row(row),
+        House::Col(col),
+        House::square_of(row, col, size),
+    ]
+    .into_iter()
+    .find(|house| {
+        let house_cells = house.cells(size);
+        cells.iter().all(|cell| house_cells.contains(cell))
+    })
+    .into_iter()
+    .collect()
+}
+
+/**
+Parses a single step written in HoDoKu's notation.
+@param size the size of the board the step refers to.
+*/
+pub fn parse_step(line: &str, size: usize) -> StrResult<SolveStep> {
+    let (name, body) = line
+        .split_once(':')
+        .ok_or(format!("Missing technique name in `{line}`"))?;
+    let technique = parse_technique(name)?;
+
+    let (pattern, effects) = match body.split_once("=>") {
+        Some((pattern, effects)) => (pattern.trim(), effects.trim()),
+        None => ("", body.trim()),
+    };
+
+    let (placements, eliminations) = parse_effects(effects, size)?;
+
+    let mut step = SolveStep {
+        technique,
+        base: vec![],
+        cover: vec![],
+        cells: vec![],
+        digits: vec![],
+        placements,
+        eliminations,
+    };
+
+    match technique {
+        Technique::NakedSingle | Technique::HiddenSingle => {
+            let placement = step
+                .placements
+                .first()
+                .ok_or(format!("Missing placement in `{line}`"))?;
+            step.cells = vec![(placement.row, placement.col)];
+            step.digits = vec![placement.value];
+        }
+        Technique::LockedCandidatesPointing | Technique::LockedCandidatesClaiming => {
+            let (digits, house) = pattern
+                .split_once(" in ")
+                .ok_or(format!("Invalid pattern `{pattern}`"))?;
+            step.digits = parse_digits(digits, size)?;
+            step.base = parse_houses(house.trim(), size)?;
+            let eliminated: Vec<(usize, usize)> =
+                step.eliminations.iter().map(|e| (e.row, e.col)).collect();
+            step.cover = match (technique, eliminated.first()) {
+                (Technique::LockedCandidatesClaiming, Some(&(row, col))) => {
+                    vec![House::square_of(row, col, size)]
+                }
+                // the line the pointing cells share, through the base box and every elimination
+                (_, Some(&(row, col))) => {
+                    let base_cells: Vec<(usize, usize)> = step
+                        .base
+                        .iter()
+                        .flat_map(|house| house.cells(size))
+                        .collect();
+
+                    [House::Row(row), House::Col(col)]
+                        .into_iter()
+                        .find(|line| {
+                            let cells = line.cells(size);
+                            cells.iter().any(|cell| base_cells.contains(cell))
+                                && eliminated.iter().all(|cell| cells.contains(cell))
+                        })
+                        .into_iter()
+                        .collect()
+                }
+                (_, None) => vec![],
+            };
+        }
+        Technique::XWing | Technique::Swordfish => {
+            let parts: Vec<&str> = pattern.split_whitespace().collect();
+            if parts.len() != 3 {
+                return Err(format!("Invalid pattern `{pattern}`"));
+            }
+            step.digits = parse_digits(parts[0], size)?;
+            step.base = parse_houses(parts[1], size)?;
+            step.cover = parse_houses(parts[2], size)?;
+        }
+        _ => {
+            let (digits, cells) = pattern
+                .split_once(" in ")
+                .ok_or(format!("Invalid pattern `{pattern}`"))?;
+            step.digits = parse_digits(digits, size)?;
+            step.cells = parse_cells(cells, size)?;
+            step.base = common_houses(&step.cells, size);
+        }
+    }
+
+    Ok(step)
+}
+
+pub fn parse_steps(text: &str, size: usize) -> StrResult<Vec<SolveStep>> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_step(line, size))
+        .collect()
+}
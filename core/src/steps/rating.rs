@@ -0,0 +1,93 @@
+use crate::board::Board;
+use crate::steps::{trace_solve, Technique};
+use crate::types::StrResult;
+use std::ops::RangeInclusive;
+
+// the score of a board the techniques can't solve
+pub const UNSOLVED_SCORE: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+    // needs guessing
+    Extreme,
+}
+
+impl Difficulty {
+    pub fn from_score(score: u32) -> Difficulty {
+        [
+            Difficulty::Easy,
+            Difficulty::Medium,
+            Difficulty::Hard,
+            Difficulty::Expert,
+        ]
+        .into_iter()
+        .find(|difficulty| difficulty.score_range().contains(&score))
+        .unwrap_or(Difficulty::Extreme)
+    }
+
+    pub fn score_range(&self) -> RangeInclusive<u32> {
+        match self {
+            Difficulty::Easy => 0..=19,
+            Difficulty::Medium => 20..=39,
+            Difficulty::Hard => 40..=79,
+            Difficulty::Expert => 80..=UNSOLVED_SCORE - 1,
+            Difficulty::Extreme => UNSOLVED_SCORE..=u32::MAX,
+        }
+    }
+}
+
+impl Technique {
+    pub fn score(&self) -> u32 {
+        match self {
+            Technique::NakedSingle => 1,
+            Technique::HiddenSingle => 5,
+            Technique::LockedCandidatesPointing => 20,
+            Technique::LockedCandidatesClaiming => 22,
+            Technique::NakedPair => 40,
+            Technique::HiddenPair => 45,
+            Technique::NakedTriple => 55,
+            Technique::HiddenTriple => 60,
+            Technique::XWing => 80,
+            Technique::Swordfish => 90,
+        }
+    }
+}
+
+/**
+- `score` - the score of the hardest technique needed, or `UNSOLVED_SCORE`.
+- `steps` - the number of steps the solve took.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rating {
+    pub score: u32,
+    pub difficulty: Difficulty,
+    pub hardest: Option<Technique>,
+    pub steps: usize,
+    pub solved: bool,
+}
+
+/**
+Rates the board by the hardest technique a person needs to solve it.
+*/
+pub fn rate(board: &Board) -> StrResult<Rating> {
+    let trace = trace_solve(board)?;
+    let hardest = trace.hardest_technique();
+
+    let score = match (trace.solved, hardest) {
+        (false, _) => UNSOLVED_SCORE,
+        (true, Some(technique)) => technique.score(),
+        (true, None) => 0,
+    };
+
+    Ok(Rating {
+        score,
+        difficulty: Difficulty::from_score(score),
+        hardest,
+        steps: trace.steps.len(),
+        solved: trace.solved,
+    })
+}
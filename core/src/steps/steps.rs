@@ -0,0 +1,228 @@
+use crate::analyze::{analyze_cell, recalculate_cell, AnalyzedBoard, AnalyzedCell};
+use crate::board::{Board, BoardData};
+use crate::types::{PositionalValue, StrResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum House {
+    Row(usize),
+    Col(usize),
+    // 1D index of the square, same as `Board::get_square_1d`
+    Square(usize),
+}
+
+impl House {
+    pub fn cells(&self, size: usize) -> Vec<(usize, usize)> {
+        let square_size = (size as f32).sqrt().floor() as usize;
+
+        match *self {
+            House::Row(row) => (0..size).map(|col| (row, col)).collect(),
+            House::Col(col) => (0..size).map(|row| (row, col)).collect(),
+            House::Square(index) => {
+                let start_row = index / square_size * square_size;
+                let start_col = index % square_size * square_size;
+                (0..size)
+                    .map(|i| (start_row + i / square_size, start_col + i % square_size))
+                    .collect()
+            }
+        }
+    }
+
+    pub fn square_of(row: usize, col: usize, size: usize) -> House {
+        let square_size = (size as f32).sqrt().floor() as usize;
+        House::Square(row / square_size * square_size + col / square_size)
+    }
+}
+
+/// Techniques ordered from the easiest to the hardest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Technique {
+    NakedSingle,
+    HiddenSingle,
+    LockedCandidatesPointing,
+    LockedCandidatesClaiming,
+    NakedPair,
+    HiddenPair,
+    NakedTriple,
+    HiddenTriple,
+    XWing,
+    Swordfish,
+}
+
+impl Technique {
+    pub const ALL: [Technique; 10] = [
+        Technique::NakedSingle,
+        Technique::HiddenSingle,
+        Technique::LockedCandidatesPointing,
+        Technique::LockedCandidatesClaiming,
+        Technique::NakedPair,
+        Technique::HiddenPair,
+        Technique::NakedTriple,
+        Technique::HiddenTriple,
+        Technique::XWing,
+        Technique::Swordfish,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Technique::NakedSingle => "Naked Single",
+            Technique::HiddenSingle => "Hidden Single",
+            Technique::LockedCandidatesPointing => "Locked Candidates Type 1 (Pointing)",
+            Technique::LockedCandidatesClaiming => "Locked Candidates Type 2 (Claiming)",
+            Technique::NakedPair => "Naked Pair",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::XWing => "X-Wing",
+            Technique::Swordfish => "Swordfish",
+        }
+    }
+
+    pub fn is_single(&self) -> bool {
+        matches!(self, Technique::NakedSingle | Technique::HiddenSingle)
+    }
+}
+
+/**
+A single logical deduction.
+- `base` - the houses the pattern was found in (empty for a naked single).
+- `cover` - the houses the eliminations happen in (locked candidates and fish only).
+- `cells` - the cells forming the pattern.
+- `digits` - the digits forming the pattern.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveStep {
+    pub technique: Technique,
+    pub base: Vec<House>,
+    pub cover: Vec<House>,
+    pub cells: Vec<(usize, usize)>,
+    pub digits: Vec<usize>,
+    pub placements: Vec<PositionalValue<usize>>,
+    pub eliminations: Vec<PositionalValue<usize>>,
+}
+
+/**
+Analyzes the board like `analyze_board`, but keeps cells with a single option as
+`Undetermined` so the naked single can be reported as a step of its own.
+*/
+pub fn candidates_board(board: &Board) -> StrResult<AnalyzedBoard> {
+    let size = board.get_size();
+    let mut data: BoardData<AnalyzedCell> = vec![];
+
+    for row in 0..size {
+        let mut row_list: Vec<AnalyzedCell> = vec![];
+        for col in 0..size {
+            let cell = match analyze_cell(board, row, col)? {
+                AnalyzedCell::Value(v) if *board.at(row, col).unwrap() == 0 => {
+                    AnalyzedCell::Undetermined(vec![v])
+                }
+                // sorted so the steps found don't depend on the HashSet order
+                AnalyzedCell::Undetermined(mut options) => {
+                    options.sort();
+                    AnalyzedCell::Undetermined(options)
+                }
+                cell => cell,
+            };
+            row_list.push(cell);
+        }
+        data.push(row_list);
+    }
+
+    Board::from(&data)
+}
+
+// drops every option that is already taken in the row, col or square, keeping previous eliminations.
+pub(crate) fn refresh_candidates(board: &mut AnalyzedBoard) -> StrResult<()> {
+    let size = board.get_size();
+
+    for row in 0..size {
+        for col in 0..size {
+            let current = match board.at(row, col).unwrap() {
+                AnalyzedCell::Value(_) => continue,
+                AnalyzedCell::Undetermined(options) => options.clone(),
+            };
+
+            let fresh = match recalculate_cell(board, row, col)? {
+                Some(AnalyzedCell::Value(v)) => vec![v],
+                Some(AnalyzedCell::Undetermined(options)) => options,
+                None => continue,
+            };
+
+            let mut options: Vec<usize> =
+                current.into_iter().filter(|v| fresh.contains(v)).collect();
+            options.sort();
+
+            if options.is_empty() {
+                return Err(format!(
+                    "cell ({row},{col}) has no options left, hence invalid"
+                ));
+            }
+
+            board.set(row, col, AnalyzedCell::Undetermined(options))?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn apply_step(board: &mut AnalyzedBoard, step: &SolveStep) -> StrResult<()> {
+    for PositionalValue { row, col, value } in step.placements.iter() {
+        let cell = board
+            .at(*row, *col)
+            .ok_or(format!("could not get cell of ({row},{col})"))?;
+
+        match cell {
+            AnalyzedCell::Value(v) if v == value => continue,
+            AnalyzedCell::Undetermined(options) if options.contains(value) => {}
+            _ => {
+                return Err(format!(
+                    "Could not place {value} in ({row},{col}): not an option"
+                ))
+            }
+        }
+
+        board.set(*row, *col, AnalyzedCell::Value(*value))?;
+    }
+
+    for PositionalValue { row, col, value } in step.eliminations.iter() {
+        let cell = board
+            .at(*row, *col)
+            .ok_or(format!("could not get cell of ({row},{col})"))?;
+
+        let options = match cell {
+            AnalyzedCell::Undetermined(options) => options,
+            AnalyzedCell::Value(v) if v != value => continue,
+            AnalyzedCell::Value(_) => {
+                return Err(format!(
+                    "Could not eliminate {value} from ({row},{col}): already placed"
+                ))
+            }
+        };
+
+        let options: Vec<usize> = options.iter().filter(|&v| v != value).copied().collect();
+
+        if options.is_empty() {
+            return Err(format!(
+                "cell ({row},{col}) has no options left, hence invalid"
+            ));
+        }
+
+        board.set(*row, *col, AnalyzedCell::Undetermined(options))?;
+    }
+
+    refresh_candidates(board)
+}
+
+// converts an analyzed board to a numerical one, leaving undetermined cells empty.
+pub(crate) fn to_partial_board(board: &AnalyzedBoard) -> StrResult<Board> {
+    let data: BoardData = board
+        .get_rows()
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell.get_value().unwrap_or(0))
+                .collect()
+        })
+        .collect();
+
+    Board::from(&data)
+}
@@ -0,0 +1,344 @@
+use crate::analyze::{AnalyzedBoard, AnalyzedCell};
+use crate::combinatorics::combinations;
+use crate::infer::{infer_col, infer_row, infer_square};
+use crate::steps::{House, SolveStep, Technique};
+use crate::types::{PositionalValue, StrResult};
+
+fn options_at(board: &AnalyzedBoard, row: usize, col: usize) -> Option<&Vec<usize>> {
+    board.at(row, col).and_then(|cell| cell.get_undetermined())
+}
+
+fn all_houses(size: usize) -> Vec<House> {
+    let mut houses: Vec<House> = (0..size).map(House::Square).collect();
+    houses.extend((0..size).map(House::Row));
+    houses.extend((0..size).map(House::Col));
+    houses
+}
+
+fn empty_step(technique: Technique) -> SolveStep {
+    SolveStep {
+        technique,
+        base: vec![],
+        cover: vec![],
+        cells: vec![],
+        digits: vec![],
+        placements: vec![],
+        eliminations: vec![],
+    }
+}
+
+fn find_naked_single(board: &AnalyzedBoard) -> Option<SolveStep> {
+    let found = board
+        .find(|cell| matches!(cell, AnalyzedCell::Undetermined(options) if options.len() == 1))?;
+    let value = found.value.get_undetermined().unwrap()[0];
+    let (row, col) = (found.row, found.col);
+
+    Some(SolveStep {
+        cells: vec![(row, col)],
+        digits: vec![value],
+        placements: vec![PositionalValue { row, col, value }],
+        ..empty_step(Technique::NakedSingle)
+    })
+}
+
+fn find_hidden_single(board: &AnalyzedBoard) -> StrResult<Option<SolveStep>> {
+    let size = board.get_size();
+    let square_size = board.get_square_size();
+
+    for house in all_houses(size) {
+        let inferred = match house {
+            House::Row(index) => infer_row(board, index)?,
+            House::Col(index) => infer_col(board, index)?,
+            House::Square(index) => infer_square(board, index / square_size, index % square_size)?,
+        };
+
+        // only the first inferred position is a single of the current board,
+        // the rest are found by infer_group after assuming it.
+        if let Some(position) = inferred.into_iter().next() {
+            return Ok(Some(SolveStep {
+                base: vec![house],
+                cells: vec![(position.row, position.col)],
+                digits: vec![position.value],
+                placements: vec![position],
+                ..empty_step(Technique::HiddenSingle)
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+fn digit_cells(board: &AnalyzedBoard, house: &House, digit: usize) -> Vec<(usize, usize)> {
+    house
+        .cells(board.get_size())
+        .into_iter()
+        .filter(|&(row, col)| options_at(board, row, col).is_some_and(|o| o.contains(&digit)))
+        .collect()
+}
+
+fn eliminations_in(
+    board: &AnalyzedBoard,
+    house: &House,
+    digit: usize,
+    excluded: &[(usize, usize)],
+) -> Vec<PositionalValue<usize>> {
+    digit_cells(board, house, digit)
+        .into_iter()
+        .filter(|cell| !excluded.contains(cell))
+        .map(|(row, col)| PositionalValue {
+            row,
+            col,
+            value: digit,
+        })
+        .collect()
+}
+
+fn find_locked_candidates(board: &AnalyzedBoard, pointing: bool) -> Option<SolveStep> {
+    let size = board.get_size();
+
+    let bases: Vec<House> = if pointing {
+        (0..size).map(House::Square).collect()
+    } else {
+        (0..size)
+            .map(House::Row)
+            .chain((0..size).map(House::Col))
+            .collect()
+    };
+
+    for base in bases {
+        for digit in 1..=size {
+            let cells = digit_cells(board, &base, digit);
+
+            if cells.len() < 2 {
+                continue;
+            }
+
+            let (first_row, first_col) = cells[0];
+            let covers: Vec<House> = if pointing {
+                vec![House::Row(first_row), House::Col(first_col)]
+            } else {
+                vec![House::square_of(first_row, first_col, size)]
+            };
+
+            for cover in covers {
+                let cover_cells = cover.cells(size);
+
+                if !cells.iter().all(|cell| cover_cells.contains(cell)) {
+                    continue;
+                }
+
+                let eliminations = eliminations_in(board, &cover, digit, &cells);
+
+                if !eliminations.is_empty() {
+                    let technique = if pointing {
+                        Technique::LockedCandidatesPointing
+                    } else {
+                        Technique::LockedCandidatesClaiming
+                    };
+
+                    return Some(SolveStep {
+                        base: vec![base],
+                        cover: vec![cover],
+                        cells,
+                        digits: vec![digit],
+                        eliminations,
+                        ..empty_step(technique)
+                    });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn find_naked_subset(board: &AnalyzedBoard, technique: Technique, k: usize) -> Option<SolveStep> {
+    let size = board.get_size();
+
+    for house in all_houses(size) {
+        let house_cells = house.cells(size);
+        let candidates: Vec<usize> = (0..size)
+            .filter(|&i| {
+                let (row, col) = house_cells[i];
+                options_at(board, row, col).is_some_and(|o| o.len() >= 2 && o.len() <= k)
+            })
+            .collect();
+
+        for combo in combinations(&candidates, k) {
+            let cells: Vec<(usize, usize)> = combo.iter().map(|&i| house_cells[i]).collect();
+            let mut digits: Vec<usize> = cells
+                .iter()
+                .flat_map(|&(row, col)| options_at(board, row, col).unwrap().clone())
+                .collect();
+            digits.sort();
+            digits.dedup();
+
+            if digits.len() != k {
+                continue;
+            }
+
+            let eliminations: Vec<PositionalValue<usize>> = digits
+                .iter()
+                .flat_map(|&digit| eliminations_in(board, &house, digit, &cells))
+                .collect();
+
+            if !eliminations.is_empty() {
+                return Some(SolveStep {
+                    base: vec![house],
+                    cells,
+                    digits,
+                    eliminations,
+                    ..empty_step(technique)
+                });
+            }
+        }
+    }
+
+    None
+}
+
+fn find_hidden_subset(board: &AnalyzedBoard, technique: Technique, k: usize) -> Option<SolveStep> {
+    let size = board.get_size();
+
+    for house in all_houses(size) {
+        let open_digits: Vec<usize> = (1..=size)
+            .filter(|&digit| !digit_cells(board, &house, digit).is_empty())
+            .collect();
+
+        for digits in combinations(&open_digits, k) {
+            let mut cells: Vec<(usize, usize)> = digits
+                .iter()
+                .flat_map(|&digit| digit_cells(board, &house, digit))
+                .collect();
+            cells.sort();
+            cells.dedup();
+
+            if cells.len() != k {
+                continue;
+            }
+
+            let eliminations: Vec<PositionalValue<usize>> = cells
+                .iter()
+                .flat_map(|&(row, col)| {
+                    options_at(board, row, col)
+                        .unwrap()
+                        .iter()
+                        .filter(|value| !digits.contains(value))
+                        .map(move |&value| PositionalValue { row, col, value })
+                })
+                .collect();
+
+            if !eliminations.is_empty() {
+                return Some(SolveStep {
+                    base: vec![house],
+                    cells,
+                    digits,
+                    eliminations,
+                    ..empty_step(technique)
+                });
+            }
+        }
+    }
+
+    None
+}
+
+fn find_fish(board: &AnalyzedBoard, technique: Technique, k: usize) -> Option<SolveStep> {
+    let size = board.get_size();
+    let lines: Vec<usize> = (0..size).collect();
+
+    for digit in 1..=size {
+        for by_row in [true, false] {
+            let to_house = |i: usize, base: bool| {
+                if base == by_row {
+                    House::Row(i)
+                } else {
+                    House::Col(i)
+                }
+            };
+
+            let positions: Vec<Vec<usize>> = lines
+                .iter()
+                .map(|&i| {
+                    digit_cells(board, &to_house(i, true), digit)
+                        .into_iter()
+                        .map(|(row, col)| if by_row { col } else { row })
+                        .collect()
+                })
+                .collect();
+
+            let base_lines: Vec<usize> = lines
+                .iter()
+                .copied()
+                .filter(|&i| positions[i].len() >= 2 && positions[i].len() <= k)
+                .collect();
+
+            for base in combinations(&base_lines, k) {
+                let mut cover: Vec<usize> =
+                    base.iter().flat_map(|&i| positions[i].clone()).collect();
+                cover.sort();
+                cover.dedup();
+
+                if cover.len() != k {
+                    continue;
+                }
+
+                let cells: Vec<(usize, usize)> = base
+                    .iter()
+                    .flat_map(|&i| to_house(i, true).cells(size))
+                    .filter(|&(row, col)| {
+                        options_at(board, row, col).is_some_and(|o| o.contains(&digit))
+                    })
+                    .collect();
+
+                let eliminations: Vec<PositionalValue<usize>> = cover
+                    .iter()
+                    .flat_map(|&i| eliminations_in(board, &to_house(i, false), digit, &cells))
+                    .collect();
+
+                if !eliminations.is_empty() {
+                    return Some(SolveStep {
+                        base: base.iter().map(|&i| to_house(i, true)).collect(),
+                        cover: cover.iter().map(|&i| to_house(i, false)).collect(),
+                        cells,
+                        digits: vec![digit],
+                        eliminations,
+                        ..empty_step(technique)
+                    });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+pub fn find_technique(board: &AnalyzedBoard, technique: Technique) -> StrResult<Option<SolveStep>> {
+    let step = match technique {
+        Technique::NakedSingle => find_naked_single(board),
+        Technique::HiddenSingle => find_hidden_single(board)?,
+        Technique::LockedCandidatesPointing => find_locked_candidates(board, true),
+        Technique::LockedCandidatesClaiming => find_locked_candidates(board, false),
+        Technique::NakedPair => find_naked_subset(board, technique, 2),
+        Technique::HiddenPair => find_hidden_subset(board, technique, 2),
+        Technique::NakedTriple => find_naked_subset(board, technique, 3),
+        Technique::HiddenTriple => find_hidden_subset(board, technique, 3),
+        Technique::XWing => find_fish(board, technique, 2),
+        Technique::Swordfish => find_fish(board, technique, 3),
+    };
+
+    Ok(step)
+}
+
+/**
+@returns the easiest step available using only the given techniques.
+*/
+pub fn find_step(board: &AnalyzedBoard, techniques: &[Technique]) -> StrResult<Option<SolveStep>> {
+    for technique in Technique::ALL.iter().filter(|t| techniques.contains(t)) {
+        if let Some(step) = find_technique(board, *technique)? {
+            return Ok(Some(step));
+        }
+    }
+
+    Ok(None)
+}
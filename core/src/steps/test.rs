@@ -0,0 +1,213 @@
+use crate::analyze::{AnalyzedBoard, AnalyzedCell};
+use crate::board::{Board, BoardData};
+use crate::steps::{
+    apply_step, candidates_board, find_technique, format_step, format_steps, parse_step,
+    parse_steps, rate, trace_solve, trace_solve_with, Difficulty, House, SolveStep, Technique,
+};
+use crate::test_utils::{board_from_str, EASY_PUZZLE, EASY_SOLUTION};
+use crate::types::{PositionalValue, StrResult};
+
+#[test]
+fn trace_solves_easy_board_with_singles() -> StrResult<()> {
+    let board = board_from_str(EASY_PUZZLE);
+
+    let trace = trace_solve(&board)?;
+
+    assert!(trace.solved);
+    assert_eq!(
+        trace.board.get_rows(),
+        board_from_str(EASY_SOLUTION).get_rows()
+    );
+    assert!(trace.steps.iter().all(|step| step.technique.is_single()));
+    Ok(())
+}
+
+#[test]
+fn trace_stops_without_techniques() -> StrResult<()> {
+    let board = board_from_str(EASY_PUZZLE);
+
+    let trace = trace_solve_with(&board, &[])?;
+
+    assert!(!trace.solved);
+    assert!(trace.steps.is_empty());
+    assert_eq!(trace.board.get_rows(), board.get_rows());
+    Ok(())
+}
+
+#[test]
+fn candidates_keep_naked_singles_undetermined() -> StrResult<()> {
+    let mut board = Board::new(4)?;
+    board.set(0, 0, 1)?;
+    board.set(0, 1, 2)?;
+    board.set(0, 2, 3)?;
+
+    let analyzed = candidates_board(&board)?;
+
+    assert_eq!(
+        analyzed.at(0, 3),
+        Some(&AnalyzedCell::Undetermined(vec![4]))
+    );
+    Ok(())
+}
+
+#[test]
+fn should_find_naked_pair() -> StrResult<()> {
+    let any = AnalyzedCell::Undetermined(vec![1, 2, 3, 4]);
+    let mut data: BoardData<AnalyzedCell> = vec![vec![any; 4]; 4];
+    data[0][0] = AnalyzedCell::Undetermined(vec![1, 2]);
+    data[0][1] = AnalyzedCell::Undetermined(vec![1, 2]);
+    let board: AnalyzedBoard = Board::from(&data)?;
+
+    let step = find_technique(&board, Technique::NakedPair)?.expect("Naked pair not found");
+
+    assert_eq!(step.base, vec![House::Square(0)]);
+    assert_eq!(step.digits, vec![1, 2]);
+    assert_eq!(step.cells, vec![(0, 0), (0, 1)]);
+    assert_eq!(step.eliminations.len(), 4);
+    Ok(())
+}
+
+#[test]
+fn apply_step_rejects_missing_option() -> StrResult<()> {
+    let board = board_from_str(EASY_PUZZLE);
+    let mut analyzed = candidates_board(&board)?;
+    let step = SolveStep {
+        technique: Technique::NakedSingle,
+        base: vec![],
+        cover: vec![],
+        cells: vec![(0, 2)],
+        digits: vec![5],
+        placements: vec![PositionalValue {
+            row: 0,
+            col: 2,
+            value: 5,
+        }],
+        eliminations: vec![],
+    };
+
+    assert!(apply_step(&mut analyzed, &step).is_err());
+    Ok(())
+}
+
+#[test]
+fn should_format_naked_pair() {
+    let step = SolveStep {
+        technique: Technique::NakedPair,
+        base: vec![House::Row(0)],
+        cover: vec![],
+        cells: vec![(0, 1), (0, 4)],
+        digits: vec![3, 7],
+        placements: vec![],
+        eliminations: vec![PositionalValue {
+            row: 0,
+            col: 7,
+            value: 3,
+        }],
+    };
+
+    assert_eq!(
+        format_step(&step, 9),
+        "Naked Pair: 3,7 in r1c2,r1c5 => r1c8<>3"
+    );
+    assert_eq!(
+        parse_step("Naked Pair: 3,7 in r1c2,r1c5 => r1c8<>3", 9),
+        Ok(step)
+    );
+}
+
+#[test]
+fn should_parse_compact_notation() -> StrResult<()> {
+    let step = parse_step(
+        "Locked Candidates Type 1 (Pointing): 5 in b1 => r3c56<>5",
+        9,
+    )?;
+
+    assert_eq!(step.technique, Technique::LockedCandidatesPointing);
+    assert_eq!(step.base, vec![House::Square(0)]);
+    assert_eq!(step.cover, vec![House::Row(2)]);
+    assert_eq!(
+        step.eliminations,
+        vec![
+            PositionalValue {
+                row: 2,
+                col: 4,
+                value: 5
+            },
+            PositionalValue {
+                row: 2,
+                col: 5,
+                value: 5
+            },
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn pointing_cover_is_line_through_box() -> StrResult<()> {
+    let step = parse_step("Locked Candidates Type 1 (Pointing): 4 in b1 => r5c2<>4", 9)?;
+
+    assert_eq!(step.cover, vec![House::Col(1)]);
+    Ok(())
+}
+
+#[test]
+fn fish_round_trips_through_notation() -> StrResult<()> {
+    // as exported by HoDoKu
+    let line = "X-Wing: 1 r28 c47 => r1c4,r4c4,r7c7<>1";
+    let step = parse_step(line, 9)?;
+
+    assert_eq!(step.base, vec![House::Row(1), House::Row(7)]);
+    assert_eq!(step.cover, vec![House::Col(3), House::Col(6)]);
+    assert_eq!(
+        format_step(&step, 9),
+        "X-Wing: 1 r28 c47 => r1c4<>1, r4c4<>1, r7c7<>1"
+    );
+    assert_eq!(parse_step(&format_step(&step, 9), 9)?, step);
+
+    // bigger boards can't write the numbers of their houses together
+    assert!(format_step(&step, 16).contains("1 r2,r8 c4,c7"));
+    assert_eq!(parse_step(&format_step(&step, 16), 16)?, step);
+    Ok(())
+}
+
+#[test]
+fn should_fail_parsing_unknown_technique() {
+    assert!(parse_step("Death Blossom: 1 in r1c1 => r2c2<>1", 9).is_err());
+    assert!(parse_step("Naked Single: r10c1=1", 9).is_err());
+}
+
+#[test]
+fn exported_trace_replays_on_board() -> StrResult<()> {
+    let board = board_from_str(EASY_PUZZLE);
+    let trace = trace_solve(&board)?;
+
+    let steps = parse_steps(&format_steps(&trace.steps, 9), 9)?;
+    let mut analyzed = candidates_board(&board)?;
+    for step in steps.iter() {
+        apply_step(&mut analyzed, step)?;
+    }
+
+    assert_eq!(steps.len(), trace.steps.len());
+    assert!(analyzed.get_rows_flat().iter().all(|cell| cell.is_value()));
+    Ok(())
+}
+
+#[test]
+fn should_rate_easy_board() -> StrResult<()> {
+    let rating = rate(&board_from_str(EASY_PUZZLE))?;
+
+    assert!(rating.solved);
+    assert_eq!(rating.difficulty, Difficulty::Easy);
+    assert_eq!(rating.score, rating.hardest.unwrap().score());
+    Ok(())
+}
+
+#[test]
+fn empty_board_is_extreme() -> StrResult<()> {
+    let rating = rate(&Board::new(9)?)?;
+
+    assert!(!rating.solved);
+    assert_eq!(rating.difficulty, Difficulty::Extreme);
+    Ok(())
+}
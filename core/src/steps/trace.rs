@@ -0,0 +1,53 @@
+use crate::analyze::is_full_board;
+use crate::board::Board;
+use crate::steps::{
+    apply_step, candidates_board, find_step, to_partial_board, SolveStep, Technique,
+};
+use crate::types::StrResult;
+
+/**
+The result of solving a board the way a person would.
+- `board` - the board after applying all the steps, unsolved cells are left empty.
+- `solved` - whether the steps were enough to fill the whole board.
+*/
+#[derive(Debug, Clone)]
+pub struct SolveTrace {
+    pub steps: Vec<SolveStep>,
+    pub board: Board,
+    pub solved: bool,
+}
+
+impl SolveTrace {
+    pub fn hardest_technique(&self) -> Option<Technique> {
+        self.steps.iter().map(|step| step.technique).max()
+    }
+}
+
+pub fn trace_solve(board: &Board) -> StrResult<SolveTrace> {
+    trace_solve_with(board, &Technique::ALL)
+}
+
+/**
+Solves the board step by step, always applying the easiest available step.
+Stops when the board is full or none of the given techniques applies.
+*/
+pub fn trace_solve_with(board: &Board, techniques: &[Technique]) -> StrResult<SolveTrace> {
+    let mut analyzed_board = candidates_board(board)?;
+    let mut steps: Vec<SolveStep> = Vec::new();
+
+    while !is_full_board(&analyzed_board) {
+        let step = match find_step(&analyzed_board, techniques)? {
+            Some(step) => step,
+            None => break,
+        };
+
+        apply_step(&mut analyzed_board, &step)?;
+        steps.push(step);
+    }
+
+    Ok(SolveTrace {
+        steps,
+        board: to_partial_board(&analyzed_board)?,
+        solved: is_full_board(&analyzed_board),
+    })
+}
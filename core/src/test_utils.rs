@@ -0,0 +1,20 @@
+use crate::board::{Board, BoardData};
+
+// parses a row-major string of digits where `0` or `.` is an empty cell.
+pub fn board_from_str(puzzle: &str) -> Board {
+    let values: Vec<usize> = puzzle
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_digit(10).unwrap_or(0) as usize)
+        .collect();
+    let size = (values.len() as f32).sqrt().floor() as usize;
+    let data: BoardData = values.chunks(size).map(|row| row.to_vec()).collect();
+
+    Board::from(&data).expect("Failed creating board from string")
+}
+
+pub const EASY_PUZZLE: &str =
+    "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+
+pub const EASY_SOLUTION: &str =
+    "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
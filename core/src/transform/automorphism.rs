@@ -0,0 +1,104 @@
+use crate::board::Board;
+use crate::transform::{canonical_transforms, Symmetry, Transform};
+use crate::types::StrResult;
+use std::collections::HashSet;
+
+/**
+- `automorphisms` - every transform mapping the board onto itself, the identity included.
+  Digits missing from the board are never relabeled.
+- `layout` - the symmetries of the clue positions, ignoring their values.
+- `relabeled` - the layout symmetries that some automorphism follows, with that automorphism.
+  These are the boards Gurth's symmetrical placement applies to.
+*/
+#[derive(Debug, Clone)]
+pub struct SymmetryReport {
+    pub automorphisms: Vec<Transform>,
+    pub layout: Vec<Symmetry>,
+    pub relabeled: Vec<(Symmetry, Transform)>,
+}
+
+fn geometric_transform(symmetry: Symmetry, size: usize) -> StrResult<Option<Transform>> {
+    Ok(Some(match symmetry {
+        Symmetry::None => return Ok(None),
+        Symmetry::Rotational180 => Transform::rotate_180(size)?,
+        Symmetry::Rotational90 => Transform::rotate_90(size)?,
+        Symmetry::MirrorHorizontal => Transform::mirror_horizontal(size)?,
+        Symmetry::MirrorVertical => Transform::mirror_vertical(size)?,
+        Symmetry::Diagonal => Transform::transpose(size)?,
+        Symmetry::AntiDiagonal => Transform::anti_transpose(size)?,
+    }))
+}
+
+/**
+@returns every transform that maps `board` onto itself, relabeling included.
+Fails for boards with more than a few thousand of them, such as nearly empty ones.
+*/
+pub fn automorphisms(board: &Board) -> StrResult<Vec<Transform>> {
+    let size = board.get_size();
+    let transforms = canonical_transforms(board, true)?;
+    let back = transforms[0].inverse();
+
+    let present: Vec<bool> = (1..=size)
+        .map(|value| board.find(|&v| v == value).is_some())
+        .collect();
+
+    let mut seen: HashSet<Transform> = HashSet::new();
+    let mut found: Vec<Transform> = Vec::new();
+
+    for transform in transforms {
+        let automorphism = transform.then(&back)?;
+
+        // missing digits can go anywhere, keep them in place
+        let digits: Vec<usize> = automorphism
+            .digits()
+            .iter()
+            .enumerate()
+            .map(|(i, &digit)| if present[i] { digit } else { i + 1 })
+            .collect();
+
+        let automorphism = Transform::from_parts(
+            automorphism.is_transposed(),
+            automorphism.rows().to_vec(),
+            automorphism.cols().to_vec(),
+            digits,
+        )?;
+
+        if seen.insert(automorphism.clone()) {
+            found.push(automorphism);
+        }
+    }
+
+    Ok(found)
+}
+
+/**
+Reports the symmetries of the board, both of its whole content and of its clue layout.
+*/
+pub fn symmetry_report(board: &Board) -> StrResult<SymmetryReport> {
+    let size = board.get_size();
+    let automorphisms = automorphisms(board)?;
+
+    let layout: Vec<Symmetry> = Symmetry::ALL
+        .into_iter()
+        .filter(|&symmetry| symmetry != Symmetry::None && symmetry.matches_clues(board))
+        .collect();
+
+    let mut relabeled: Vec<(Symmetry, Transform)> = Vec::new();
+
+    for &symmetry in layout.iter() {
+        let geometric = geometric_transform(symmetry, size)?.unwrap();
+
+        if let Some(automorphism) = automorphisms
+            .iter()
+            .find(|automorphism| automorphism.same_positions(&geometric))
+        {
+            relabeled.push((symmetry, automorphism.clone()));
+        }
+    }
+
+    Ok(SymmetryReport {
+        automorphisms,
+        layout,
+        relabeled,
+    })
+}
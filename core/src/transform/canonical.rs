@@ -0,0 +1,349 @@
+use crate::board::{Board, BoardData};
+use crate::combinatorics::permutations;
+use crate::transform::Transform;
+use crate::types::StrResult;
+use crate::validators::is_square;
+use std::cmp::Ordering;
+
+// largest board whose col orders can all be tried, (3!)^4 for a 9x9
+const MAX_SIZE: usize = 9;
+
+// transforms kept when looking for all of them, nearly empty boards have millions
+const MAX_TRANSFORMS: usize = 10_000;
+
+/**
+The smallest board equivalent to a board, reading the cells row by row.
+- `transform` - turns the original board into `board`.
+*/
+#[derive(Debug, Clone)]
+pub struct CanonicalForm {
+    pub board: Board,
+    pub transform: Transform,
+}
+
+// every order of the cols that keeps the stacks together, as the col placed at each position
+fn col_orders(square_size: usize) -> Vec<Vec<usize>> {
+    let lines: Vec<usize> = (0..square_size).collect();
+    let inner = permutations(&lines);
+    let mut orders: Vec<Vec<usize>> = Vec::new();
+
+    for stacks in permutations(&lines) {
+        let mut stack_orders: Vec<Vec<usize>> = vec![vec![]];
+
+        for stack in stacks {
+            stack_orders = stack_orders
+                .into_iter()
+                .flat_map(|order| {
+                    inner.iter().map(move |cols| {
+                        let mut order = order.clone();
+                        order.extend(cols.iter().map(|col| stack * square_size + col));
+                        order
+                    })
+                })
+                .collect();
+        }
+
+        orders.extend(stack_orders);
+    }
+
+    orders
+}
+
+fn invert(order: &[usize]) -> Vec<usize> {
+    let mut inverse = vec![0; order.len()];
+
+    for (position, &line) in order.iter().enumerate() {
+        inverse[line] = position;
+    }
+
+    inverse
+}
+
+/**
+A depth first search for the smallest board under one transpose and col order,
+placing the rows one at a time and only following the rows that give the smallest result.
+*/
+struct Search<'a> {
+    size: usize,
+    square_size: usize,
+    // the cells by row, already transposed when searching the transposed board
+    cells: &'a [Vec<usize>],
+    cols: &'a [usize],
+    transpose: bool,
+    order: Vec<usize>,
+    output: Vec<usize>,
+    // keeps every transform giving the smallest board, rather than the first one
+    all: bool,
+    best: &'a mut Option<(Vec<usize>, Vec<Transform>)>,
+}
+
+type Labels = [usize; MAX_SIZE + 1];
+
+type Line = [usize; MAX_SIZE];
+
+impl Search<'_> {
+    /**
+    Reads the row with the given labels, giving new digits the next labels.
+    @returns `None` as soon as the row reads bigger than `bound`.
+    */
+    fn relabeled(
+        &self,
+        row: usize,
+        labels: &mut Labels,
+        next_label: &mut usize,
+        bound: Option<&[usize]>,
+    ) -> Option<Line> {
+        let mut line: Line = [0; MAX_SIZE];
+        let mut bound = bound;
+
+        for (i, &col) in self.cols.iter().enumerate() {
+            line[i] = match self.cells[row][col] {
+                0 => 0,
+                value => {
+                    if labels[value] == 0 {
+                        labels[value] = *next_label;
+                        *next_label += 1;
+                    }
+                    labels[value]
+                }
+            };
+
+            if let Some(limit) = bound {
+                match line[i].cmp(&limit[i]) {
+                    Ordering::Greater => return None,
+                    Ordering::Less => bound = None,
+                    Ordering::Equal => {}
+                }
+            }
+        }
+
+        Some(line)
+    }
+
+    fn candidates(&self, position: usize) -> Vec<usize> {
+        let used = &self.order[..position];
+
+        let rows: Vec<usize> = match position % self.square_size {
+            0 => (0..self.size)
+                .filter(|row| {
+                    !used
+                        .iter()
+                        .any(|u| u / self.square_size == row / self.square_size)
+                })
+                .collect(),
+            _ => {
+                let band = self.order[position - 1] / self.square_size;
+                (band * self.square_size..(band + 1) * self.square_size)
+                    .filter(|row| !used.contains(row))
+                    .collect()
+            }
+        };
+
+        if self.all {
+            return rows;
+        }
+
+        // rows of a band with the same cells give the same boards
+        let mut distinct: Vec<usize> = Vec::new();
+        for row in rows {
+            if !distinct.iter().any(|&other| {
+                other / self.square_size == row / self.square_size
+                    && self.cells[other] == self.cells[row]
+            }) {
+                distinct.push(row);
+            }
+        }
+
+        distinct
+    }
+
+    fn place(&mut self, position: usize, labels: &Labels, next_label: usize) -> StrResult<()> {
+        if position == self.size {
+            return self.finish(labels, next_label);
+        }
+
+        let start = position * self.size;
+        let mut smallest: Option<Line> = None;
+        let mut options: Vec<(usize, Labels, usize)> = Vec::new();
+
+        // while the rows so far match the best board, the next row can't read bigger than its row
+        let best_row: Option<Line> = self.best.as_ref().and_then(|(best, _)| {
+            (self.output[..start] == best[..start]).then(|| {
+                let mut line: Line = [0; MAX_SIZE];
+                line[..self.size].copy_from_slice(&best[start..start + self.size]);
+                line
+            })
+        });
+
+        for row in self.candidates(position) {
+            let mut row_labels = *labels;
+            let mut row_next = next_label;
+            let bound = smallest
+                .as_ref()
+                .or(best_row.as_ref())
+                .map(|line| &line[..]);
+
+            let line = match self.relabeled(row, &mut row_labels, &mut row_next, bound) {
+                Some(line) => line,
+                None => continue,
+            };
+
+            if smallest.is_none_or(|s| line < s) {
+                smallest = Some(line);
+                options.clear();
+            }
+
+            options.push((row, row_labels, row_next));
+        }
+
+        let smallest = match smallest {
+            Some(smallest) => smallest,
+            None => return Ok(()),
+        };
+
+        for (row, row_labels, row_next) in options {
+            // an earlier option may have found a smaller board
+            if let Some((best, _)) = self.best.as_ref() {
+                let prefix = self.output[..start]
+                    .iter()
+                    .chain(smallest[..self.size].iter());
+
+                if prefix.cmp(best[..start + self.size].iter()) == Ordering::Greater {
+                    return Ok(());
+                }
+            }
+
+            self.order.push(row);
+            self.output.extend(smallest[..self.size].iter());
+            self.place(position + 1, &row_labels, row_next)?;
+            self.output.truncate(start);
+            self.order.pop();
+        }
+
+        Ok(())
+    }
+
+    fn finish(&mut self, labels: &Labels, mut next_label: usize) -> StrResult<()> {
+        let ordering = self.best.as_ref().map(|(best, _)| self.output.cmp(best));
+
+        if ordering == Some(Ordering::Greater) || (ordering == Some(Ordering::Equal) && !self.all) {
+            return Ok(());
+        }
+
+        // digits missing from the board take the labels that are left
+        let mut digits = vec![0; self.size];
+        for value in 1..=self.size {
+            digits[value - 1] = match labels[value] {
+                0 => {
+                    next_label += 1;
+                    next_label - 1
+                }
+                label => label,
+            };
+        }
+
+        let transform = Transform::from_parts(
+            self.transpose,
+            invert(&self.order),
+            invert(self.cols),
+            digits,
+        )?;
+
+        match (ordering, self.best.as_mut()) {
+            (Some(Ordering::Equal), Some((_, transforms))) => {
+                if transforms.len() == MAX_TRANSFORMS {
+                    return Err(format!(
+                        "Board has more than {MAX_TRANSFORMS} transforms giving its canonical form"
+                    ));
+                }
+                transforms.push(transform);
+            }
+            _ => *self.best = Some((self.output.clone(), vec![transform])),
+        }
+
+        Ok(())
+    }
+}
+
+/**
+@returns the transforms turning `board` into its canonical form,
+every one of them when `all` is set, otherwise just one.
+*/
+pub(crate) fn canonical_transforms(board: &Board, all: bool) -> StrResult<Vec<Transform>> {
+    let size = board.get_size();
+    let square_size = board.get_square_size();
+
+    if size > MAX_SIZE || !is_square(size) {
+        return Err(format!(
+            "Canonical forms support boards up to {MAX_SIZE}x{MAX_SIZE}, not {size}x{size}"
+        ));
+    }
+
+    if let Some(cell) = board.find(|&v| v > size) {
+        return Err(format!(
+            "Value of {} in ({},{}) is not valid: Too big",
+            cell.value, cell.row, cell.col
+        ));
+    }
+
+    let rows: BoardData = board.get_rows().clone();
+    let cols: BoardData = (0..size)
+        .map(|i| board.get_col(i).unwrap().clone())
+        .collect();
+    let mut best: Option<(Vec<usize>, Vec<Transform>)> = None;
+
+    for (transpose, cells) in [(false, &rows), (true, &cols)] {
+        for order in col_orders(square_size) {
+            let mut search = Search {
+                size,
+                square_size,
+                cells,
+                cols: &order,
+                transpose,
+                order: Vec::new(),
+                output: Vec::new(),
+                all,
+                best: &mut best,
+            };
+
+            search.place(0, &[0; MAX_SIZE + 1], 1)?;
+        }
+    }
+
+    Ok(best.unwrap().1)
+}
+
+/**
+Finds the smallest board, read row by row, among all the boards the transforms
+and relabelings make out of `board`, where empty cells (0) come before any digit.
+Equivalent boards have the same canonical board. Supports boards up to 9x9.
+*/
+pub fn canonical_form(board: &Board) -> StrResult<CanonicalForm> {
+    let transform = canonical_transforms(board, false)?.remove(0);
+
+    Ok(CanonicalForm {
+        board: transform.apply(board)?,
+        transform,
+    })
+}
+
+/**
+@returns a transform turning `first` into `second`, or `None` when they are not equivalent.
+*/
+pub fn are_isomorphic(first: &Board, second: &Board) -> StrResult<Option<Transform>> {
+    if first.get_size() != second.get_size() {
+        return Ok(None);
+    }
+
+    let first_form = canonical_form(first)?;
+    let second_form = canonical_form(second)?;
+
+    if first_form.board.get_rows() != second_form.board.get_rows() {
+        return Ok(None);
+    }
+
+    first_form
+        .transform
+        .then(&second_form.transform.inverse())
+        .map(Some)
+}
@@ -0,0 +1,14 @@
+pub use automorphism::*;
+pub use canonical::*;
+pub use dedup::*;
+pub use symmetry::*;
+pub use transform::*;
+
+mod automorphism;
+mod canonical;
+mod dedup;
+mod symmetry;
+mod transform;
+
+#[cfg(test)]
+mod test;
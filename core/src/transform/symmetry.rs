@@ -0,0 +1,104 @@
+use crate::board::Board;
+
+/**
+A symmetry of the clue layout, givens are added and removed together with their images.
+- `MirrorHorizontal` - mirrored across the horizontal axis (top and bottom swap).
+- `MirrorVertical` - mirrored across the vertical axis (left and right swap).
+- `Diagonal` - mirrored across the main diagonal, `AntiDiagonal` across the other one.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Symmetry {
+    #[default]
+    None,
+    Rotational180,
+    Rotational90,
+    MirrorHorizontal,
+    MirrorVertical,
+    Diagonal,
+    AntiDiagonal,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 7] = [
+        Symmetry::None,
+        Symmetry::Rotational180,
+        Symmetry::Rotational90,
+        Symmetry::MirrorHorizontal,
+        Symmetry::MirrorVertical,
+        Symmetry::Diagonal,
+        Symmetry::AntiDiagonal,
+    ];
+
+    // the images of the cell that are not the cell itself, before repeating
+    fn images(&self, row: usize, col: usize, size: usize) -> Vec<(usize, usize)> {
+        let last = size - 1;
+
+        match self {
+            Symmetry::None => vec![],
+            Symmetry::Rotational180 => vec![(last - row, last - col)],
+            Symmetry::Rotational90 => vec![
+                (col, last - row),
+                (last - row, last - col),
+                (last - col, row),
+            ],
+            Symmetry::MirrorHorizontal => vec![(last - row, col)],
+            Symmetry::MirrorVertical => vec![(row, last - col)],
+            Symmetry::Diagonal => vec![(col, row)],
+            Symmetry::AntiDiagonal => vec![(last - col, last - row)],
+        }
+    }
+
+    /**
+    @returns the cell together with all its images under the symmetry.
+    */
+    pub fn orbit(&self, row: usize, col: usize, size: usize) -> Vec<(usize, usize)> {
+        let mut orbit = vec![(row, col)];
+
+        for image in self.images(row, col, size) {
+            if !orbit.contains(&image) {
+                orbit.push(image);
+            }
+        }
+
+        orbit
+    }
+
+    /**
+    Splits all the cells of the board into orbits, ordered by their first cell.
+    */
+    pub fn orbits(&self, size: usize) -> Vec<Vec<(usize, usize)>> {
+        let mut seen = vec![vec![false; size]; size];
+        let mut orbits: Vec<Vec<(usize, usize)>> = Vec::new();
+
+        for row in 0..size {
+            for col in 0..size {
+                if seen[row][col] {
+                    continue;
+                }
+
+                let orbit = self.orbit(row, col, size);
+
+                for &(r, c) in orbit.iter() {
+                    seen[r][c] = true;
+                }
+
+                orbits.push(orbit);
+            }
+        }
+
+        orbits
+    }
+
+    /**
+    @returns whether the clue positions of the board (ignoring their values) keep the symmetry.
+    */
+    pub fn matches_clues(&self, board: &Board) -> bool {
+        let size = board.get_size();
+
+        board.filter(|&v| v != 0).iter().all(|cell| {
+            self.images(cell.row, cell.col, size)
+                .into_iter()
+                .all(|(row, col)| board.at(row, col) != Some(&0))
+        })
+    }
+}
@@ -0,0 +1,284 @@
+use crate::board::Board;
+use crate::generate::{generate, GenerateOptions};
+use crate::test_utils::{board_from_str, EASY_PUZZLE, EASY_SOLUTION};
+use crate::transform::{
+    are_isomorphic, automorphisms, canonical_form, group_equivalent, symmetry_report, Symmetry,
+    Transform,
+};
+use crate::types::StrResult;
+use crate::validators::is_valid_sudoku;
+
+fn all_kinds(size: usize) -> StrResult<Vec<Transform>> {
+    Ok(vec![
+        Transform::rotate_90(size)?,
+        Transform::rotate_180(size)?,
+        Transform::rotate_270(size)?,
+        Transform::mirror_horizontal(size)?,
+        Transform::mirror_vertical(size)?,
+        Transform::transpose(size)?,
+        Transform::anti_transpose(size)?,
+        Transform::relabel(vec![9, 8, 7, 6, 5, 4, 3, 2, 1])?,
+        Transform::swap_rows(size, 3, 5)?,
+        Transform::swap_cols(size, 6, 8)?,
+        Transform::swap_bands(size, 0, 2)?,
+        Transform::swap_stacks(size, 1, 2)?,
+    ])
+}
+
+#[test]
+fn transforms_keep_boards_valid() -> StrResult<()> {
+    let solution = board_from_str(EASY_SOLUTION);
+
+    for transform in all_kinds(9)? {
+        let transformed = transform.apply(&solution)?;
+
+        assert!(transformed.is_full());
+        assert!(is_valid_sudoku(&transformed));
+    }
+    Ok(())
+}
+
+#[test]
+fn should_move_cells() -> StrResult<()> {
+    let mut board = Board::new(9)?;
+    board.set(0, 1, 5)?;
+
+    assert_eq!(Transform::rotate_90(9)?.map_cell(0, 1), (1, 8));
+    assert_eq!(Transform::rotate_270(9)?.map_cell(0, 1), (7, 0));
+    assert_eq!(Transform::anti_transpose(9)?.map_cell(0, 1), (7, 8));
+    assert_eq!(Transform::rotate_90(9)?.apply(&board)?.at(1, 8), Some(&5));
+    assert_eq!(
+        Transform::relabel(vec![2, 3, 4, 5, 6, 7, 8, 9, 1])?
+            .apply(&board)?
+            .at(0, 1),
+        Some(&6)
+    );
+    Ok(())
+}
+
+#[test]
+fn transforms_compose_and_invert() -> StrResult<()> {
+    let puzzle = board_from_str(EASY_PUZZLE);
+    let identity = Transform::identity(9)?;
+    let rotate_90 = Transform::rotate_90(9)?;
+
+    assert_eq!(rotate_90.then(&rotate_90)?, Transform::rotate_180(9)?);
+    assert_eq!(
+        rotate_90.then(&Transform::rotate_180(9)?)?,
+        Transform::rotate_270(9)?
+    );
+
+    let kinds = all_kinds(9)?;
+    for first in kinds.iter() {
+        assert_eq!(first.then(&first.inverse())?, identity);
+        assert_eq!(first.inverse().then(first)?, identity);
+
+        for second in kinds.iter() {
+            let composed = first.then(second)?;
+
+            assert_eq!(
+                composed.apply(&puzzle)?.get_rows(),
+                second.apply(&first.apply(&puzzle)?)?.get_rows()
+            );
+            assert_eq!(
+                composed
+                    .inverse()
+                    .apply(&composed.apply(&puzzle)?)?
+                    .get_rows(),
+                puzzle.get_rows()
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn should_move_any_board() -> StrResult<()> {
+    let mut marks: Board<bool> = Board::from(&vec![vec![false; 4]; 4])?;
+    marks.set(0, 0, true)?;
+
+    let moved = Transform::rotate_180(4)?.apply_positions(&marks)?;
+
+    assert_eq!(moved.at(3, 3), Some(&true));
+    assert_eq!(moved.at(0, 0), Some(&false));
+    Ok(())
+}
+
+#[test]
+fn should_reject_invalid_transforms() -> StrResult<()> {
+    assert!(Transform::swap_rows(9, 2, 3).is_err());
+    assert!(Transform::swap_cols(9, 0, 9).is_err());
+    assert!(Transform::swap_bands(9, 0, 3).is_err());
+    assert!(Transform::relabel(vec![1, 1, 2, 3]).is_err());
+    assert!(Transform::identity(5).is_err());
+    assert!(Transform::rotate_90(4)?
+        .then(&Transform::rotate_90(9)?)
+        .is_err());
+    assert!(Transform::rotate_90(4)?.apply(&Board::new(9)?).is_err());
+    Ok(())
+}
+
+#[test]
+fn equivalent_boards_share_canonical_form() -> StrResult<()> {
+    for text in [EASY_PUZZLE, EASY_SOLUTION] {
+        let board = board_from_str(text);
+        let canonical = canonical_form(&board)?;
+
+        assert_eq!(
+            canonical.transform.apply(&board)?.get_rows(),
+            canonical.board.get_rows()
+        );
+
+        for transform in all_kinds(9)? {
+            let disguised = transform.apply(&board)?;
+
+            assert_eq!(
+                canonical_form(&disguised)?.board.get_rows(),
+                canonical.board.get_rows()
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn canonical_form_is_smallest() -> StrResult<()> {
+    let canonical = canonical_form(&board_from_str(EASY_SOLUTION))?;
+    assert_eq!(
+        canonical.board.get_row(0),
+        Some(&vec![1, 2, 3, 4, 5, 6, 7, 8, 9])
+    );
+
+    let mut single = Board::new(9)?;
+    single.set(4, 4, 7)?;
+    let canonical = canonical_form(&single)?;
+    assert_eq!(canonical.board.at(8, 8), Some(&1));
+    assert_eq!(canonical.board.filter(|&v| v != 0).len(), 1);
+
+    assert!(canonical_form(&Board::new(16)?).is_err());
+    Ok(())
+}
+
+#[test]
+fn should_find_isomorphism() -> StrResult<()> {
+    let puzzle = board_from_str(EASY_PUZZLE);
+    let disguise = Transform::rotate_90(9)?
+        .then(&Transform::swap_bands(9, 0, 1)?)?
+        .then(&Transform::relabel(vec![4, 5, 6, 7, 8, 9, 1, 2, 3])?)?;
+    let disguised = disguise.apply(&puzzle)?;
+
+    let transform = are_isomorphic(&puzzle, &disguised)?.expect("Isomorphism not found");
+    assert_eq!(transform.apply(&puzzle)?.get_rows(), disguised.get_rows());
+
+    let mut changed = puzzle.clone();
+    changed.set(0, 0, 0)?;
+    assert!(are_isomorphic(&puzzle, &changed)?.is_none());
+    assert!(are_isomorphic(&puzzle, &Board::new(4)?)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn should_find_automorphisms() -> StrResult<()> {
+    // each row is the one above shifted, so the grid has many symmetries
+    let data: Vec<Vec<usize>> = (0..9)
+        .map(|r| (0..9).map(|c| ((r % 3) * 3 + r / 3 + c) % 9 + 1).collect())
+        .collect();
+    let grid = Board::from(&data)?;
+
+    let found = automorphisms(&grid)?;
+
+    assert!(found.len() > 1);
+    assert!(found.contains(&Transform::identity(9)?));
+    for automorphism in found.iter() {
+        assert_eq!(automorphism.apply(&grid)?.get_rows(), grid.get_rows());
+    }
+
+    assert_eq!(automorphisms(&board_from_str(EASY_PUZZLE))?.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn should_report_relabeled_symmetry() -> StrResult<()> {
+    // 180 degrees turns 1 into 4 and 2 into 3
+    let board = board_from_str("1000200000030004");
+    let report = symmetry_report(&board)?;
+
+    assert_eq!(report.layout, vec![Symmetry::Rotational180]);
+    assert_eq!(report.relabeled.len(), 1);
+    assert_eq!(report.relabeled[0].1.digits(), &[4, 3, 2, 1]);
+    Ok(())
+}
+
+#[test]
+fn generated_layouts_are_symmetric() -> StrResult<()> {
+    let options = GenerateOptions {
+        seed: Some(3),
+        symmetry: Symmetry::Diagonal,
+        ..Default::default()
+    };
+    let generated = generate(9, &options)?;
+
+    assert!(symmetry_report(&generated.puzzle)?
+        .layout
+        .contains(&Symmetry::Diagonal));
+    Ok(())
+}
+
+#[test]
+fn should_group_equivalent_boards() -> StrResult<()> {
+    let puzzle = board_from_str(EASY_PUZZLE);
+    let solution = board_from_str(EASY_SOLUTION);
+    let boards = vec![
+        puzzle.clone(),
+        solution.clone(),
+        Transform::rotate_90(9)?.apply(&puzzle)?,
+        Transform::swap_stacks(9, 0, 2)?.apply(&solution)?,
+        Transform::relabel(vec![2, 1, 3, 4, 5, 6, 7, 8, 9])?.apply(&puzzle)?,
+    ];
+
+    let classes = group_equivalent(&boards)?;
+
+    assert_eq!(classes.len(), 2);
+    assert_eq!(classes[0].members, vec![0, 2, 4]);
+    assert_eq!(classes[1].members, vec![1, 3]);
+    assert_eq!(classes[0].representative, 0);
+    assert_eq!(
+        classes[1].canonical_board()?.get_rows(),
+        canonical_form(&solution)?.board.get_rows()
+    );
+    Ok(())
+}
+
+#[test]
+fn should_group_large_batch() -> StrResult<()> {
+    let kinds = all_kinds(9)?;
+    let puzzle = board_from_str(EASY_PUZZLE);
+    let mut fewer_givens = puzzle.clone();
+    fewer_givens.set(0, 0, 0)?;
+
+    // every board is a different mix of the transforms, alternating between the two puzzles
+    let boards: Vec<Board> = (0..200)
+        .map(|i: usize| {
+            let mut transform = Transform::identity(9)?;
+
+            for (bit, kind) in kinds.iter().enumerate() {
+                if (i / 2) & (1 << bit) != 0 {
+                    transform = transform.then(kind)?;
+                }
+            }
+
+            transform.apply(if i.is_multiple_of(2) {
+                &puzzle
+            } else {
+                &fewer_givens
+            })
+        })
+        .collect::<StrResult<_>>()?;
+
+    let classes = group_equivalent(&boards)?;
+
+    assert_eq!(classes.len(), 2);
+    assert_eq!(classes[0].members, (0..200).step_by(2).collect::<Vec<_>>());
+    assert_eq!(classes[1].members, (1..200).step_by(2).collect::<Vec<_>>());
+    Ok(())
+}
@@ -0,0 +1,395 @@
+use crate::board::{Board, BoardData};
+use crate::types::StrResult;
+use crate::validators::is_square;
+
+/**
+A change of a board that keeps every valid sudoku valid.
+A cell at (row, col) is first transposed when `transpose` is set,
+then its row `r` moves to `rows[r]` and its col `c` to `cols[c]`,
+and a value `v` becomes `digits[v - 1]`. Empty cells stay empty.
+Row and col moves never split a band or a stack.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Transform {
+    size: usize,
+    transpose: bool,
+    rows: Vec<usize>,
+    cols: Vec<usize>,
+    digits: Vec<usize>,
+}
+
+fn square_size(size: usize) -> StrResult<usize> {
+    match is_square(size) {
+        true => Ok((size as f32).sqrt().round() as usize),
+        false => Err(format!(
+            "Could not get size that is not square number: {size}"
+        )),
+    }
+}
+
+fn identity(size: usize) -> Vec<usize> {
+    (0..size).collect()
+}
+
+fn reversed(size: usize) -> Vec<usize> {
+    (0..size).rev().collect()
+}
+
+fn invert(permutation: &[usize]) -> Vec<usize> {
+    let mut inverse = vec![0; permutation.len()];
+
+    for (from, &to) in permutation.iter().enumerate() {
+        inverse[to] = from;
+    }
+
+    inverse
+}
+
+// `first` and then `second`
+fn compose(first: &[usize], second: &[usize]) -> Vec<usize> {
+    first.iter().map(|&i| second[i]).collect()
+}
+
+fn is_permutation(values: &[usize]) -> bool {
+    let mut seen = vec![false; values.len()];
+
+    values
+        .iter()
+        .all(|&v| v < values.len() && !std::mem::replace(&mut seen[v], true))
+}
+
+// whether every band of lines lands on a single band
+fn keeps_bands(lines: &[usize], square_size: usize) -> bool {
+    lines.chunks(square_size).all(|band| {
+        band.iter()
+            .all(|&line| line / square_size == band[0] / square_size)
+    })
+}
+
+impl Transform {
+    pub fn identity(size: usize) -> StrResult<Transform> {
+        square_size(size)?;
+
+        Ok(Transform {
+            size,
+            transpose: false,
+            rows: identity(size),
+            cols: identity(size),
+            digits: (1..=size).collect(),
+        })
+    }
+
+    /**
+    A transform from its parts, see `Transform` for their meaning.
+    @param digits the new value of every value, `digits[v - 1]` for `v`.
+    */
+    pub fn from_parts(
+        transpose: bool,
+        rows: Vec<usize>,
+        cols: Vec<usize>,
+        digits: Vec<usize>,
+    ) -> StrResult<Transform> {
+        let size = rows.len();
+        let square_size = square_size(size)?;
+
+        if cols.len() != size || digits.len() != size {
+            return Err(format!(
+                "Transform parts of size {size}, {} and {} don't match",
+                cols.len(),
+                digits.len()
+            ));
+        }
+
+        if !is_permutation(&rows) || !is_permutation(&cols) {
+            return Err("Rows and cols must be moved to distinct places".to_string());
+        }
+
+        if !keeps_bands(&rows, square_size) || !keeps_bands(&cols, square_size) {
+            return Err(
+                "Rows and cols can only move together with their band or stack".to_string(),
+            );
+        }
+
+        let zero_based: Vec<usize> = digits.iter().map(|&d| d.wrapping_sub(1)).collect();
+
+        if !is_permutation(&zero_based) {
+            return Err(format!("Digits must be a relabeling of 1..={size}"));
+        }
+
+        Ok(Transform {
+            size,
+            transpose,
+            rows,
+            cols,
+            digits,
+        })
+    }
+
+    pub fn transpose(size: usize) -> StrResult<Transform> {
+        Ok(Transform {
+            transpose: true,
+            ..Transform::identity(size)?
+        })
+    }
+
+    // mirrored across the anti-diagonal, from the top right to the bottom left
+    pub fn anti_transpose(size: usize) -> StrResult<Transform> {
+        Ok(Transform {
+            transpose: true,
+            rows: reversed(size),
+            cols: reversed(size),
+            ..Transform::identity(size)?
+        })
+    }
+
+    // clockwise
+    pub fn rotate_90(size: usize) -> StrResult<Transform> {
+        Ok(Transform {
+            transpose: true,
+            cols: reversed(size),
+            ..Transform::identity(size)?
+        })
+    }
+
+    pub fn rotate_180(size: usize) -> StrResult<Transform> {
+        Ok(Transform {
+            rows: reversed(size),
+            cols: reversed(size),
+            ..Transform::identity(size)?
+        })
+    }
+
+    pub fn rotate_270(size: usize) -> StrResult<Transform> {
+        Ok(Transform {
+            transpose: true,
+            rows: reversed(size),
+            ..Transform::identity(size)?
+        })
+    }
+
+    // top and bottom swap
+    pub fn mirror_horizontal(size: usize) -> StrResult<Transform> {
+        Ok(Transform {
+            rows: reversed(size),
+            ..Transform::identity(size)?
+        })
+    }
+
+    // left and right swap
+    pub fn mirror_vertical(size: usize) -> StrResult<Transform> {
+        Ok(Transform {
+            cols: reversed(size),
+            ..Transform::identity(size)?
+        })
+    }
+
+    /**
+    @param digits the new value of every value, `digits[v - 1]` for `v`.
+    */
+    pub fn relabel(digits: Vec<usize>) -> StrResult<Transform> {
+        let size = digits.len();
+        Transform::from_parts(false, identity(size), identity(size), digits)
+    }
+
+    // swaps two groups of `group` lines, such as two bands
+    fn swapped(size: usize, first: usize, second: usize, group: usize) -> StrResult<Vec<usize>> {
+        let groups = size / group;
+
+        if first >= groups || second >= groups {
+            return Err(format!(
+                "Could not swap {first} and {second} out of {groups}"
+            ));
+        }
+
+        let mut lines = identity(size);
+
+        for i in 0..group {
+            lines.swap(first * group + i, second * group + i);
+        }
+
+        Ok(lines)
+    }
+
+    // the two rows must be in the same band
+    pub fn swap_rows(size: usize, first: usize, second: usize) -> StrResult<Transform> {
+        Transform::from_parts(
+            false,
+            Transform::swapped(size, first, second, 1)?,
+            identity(size),
+            (1..=size).collect(),
+        )
+    }
+
+    // the two cols must be in the same stack
+    pub fn swap_cols(size: usize, first: usize, second: usize) -> StrResult<Transform> {
+        Transform::from_parts(
+            false,
+            identity(size),
+            Transform::swapped(size, first, second, 1)?,
+            (1..=size).collect(),
+        )
+    }
+
+    pub fn swap_bands(size: usize, first: usize, second: usize) -> StrResult<Transform> {
+        let square_size = square_size(size)?;
+
+        Ok(Transform {
+            rows: Transform::swapped(size, first, second, square_size)?,
+            ..Transform::identity(size)?
+        })
+    }
+
+    pub fn swap_stacks(size: usize, first: usize, second: usize) -> StrResult<Transform> {
+        let square_size = square_size(size)?;
+
+        Ok(Transform {
+            cols: Transform::swapped(size, first, second, square_size)?,
+            ..Transform::identity(size)?
+        })
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_transposed(&self) -> bool {
+        self.transpose
+    }
+
+    // where each row moves to, after the transpose
+    pub fn rows(&self) -> &[usize] {
+        &self.rows
+    }
+
+    // where each col moves to, after the transpose
+    pub fn cols(&self) -> &[usize] {
+        &self.cols
+    }
+
+    // the new value of every value, `digits()[v - 1]` for `v`
+    pub fn digits(&self) -> &[usize] {
+        &self.digits
+    }
+
+    /**
+    @returns whether the cells move the same way under both transforms, whatever the digits do.
+    */
+    pub fn same_positions(&self, other: &Transform) -> bool {
+        self.transpose == other.transpose && self.rows == other.rows && self.cols == other.cols
+    }
+
+    /**
+    @returns the transform doing `self` and then `next`.
+    */
+    pub fn then(&self, next: &Transform) -> StrResult<Transform> {
+        if next.size != self.size {
+            return Err(format!(
+                "Could not compose transforms of size {} and {}",
+                self.size, next.size
+            ));
+        }
+
+        // transposing after the moves swaps which lines the moves apply to
+        let (rows, cols) = match next.transpose {
+            true => (
+                compose(&self.cols, &next.rows),
+                compose(&self.rows, &next.cols),
+            ),
+            false => (
+                compose(&self.rows, &next.rows),
+                compose(&self.cols, &next.cols),
+            ),
+        };
+
+        Ok(Transform {
+            size: self.size,
+            transpose: self.transpose != next.transpose,
+            rows,
+            cols,
+            digits: self.digits.iter().map(|&d| next.digits[d - 1]).collect(),
+        })
+    }
+
+    pub fn inverse(&self) -> Transform {
+        let (rows, cols) = match self.transpose {
+            true => (invert(&self.cols), invert(&self.rows)),
+            false => (invert(&self.rows), invert(&self.cols)),
+        };
+
+        Transform {
+            size: self.size,
+            transpose: self.transpose,
+            rows,
+            cols,
+            digits: invert(&self.digits.iter().map(|&d| d - 1).collect::<Vec<_>>())
+                .into_iter()
+                .map(|d| d + 1)
+                .collect(),
+        }
+    }
+
+    /**
+    @returns where the cell at (row, col) ends up.
+    */
+    pub fn map_cell(&self, row: usize, col: usize) -> (usize, usize) {
+        let (row, col) = match self.transpose {
+            true => (col, row),
+            false => (row, col),
+        };
+
+        (self.rows[row], self.cols[col])
+    }
+
+    // empty cells (0) stay empty, the value must be at most the size
+    pub fn map_digit(&self, value: usize) -> usize {
+        match value {
+            0 => 0,
+            value => self.digits[value - 1],
+        }
+    }
+
+    /**
+    Moves the cells of any board, leaving their values as they are.
+    */
+    pub fn apply_positions<T: Clone>(&self, board: &Board<T>) -> StrResult<Board<T>> {
+        if board.get_size() != self.size {
+            return Err(format!(
+                "Could not apply a transform of size {} to a board of size {}",
+                self.size,
+                board.get_size()
+            ));
+        }
+
+        let inverse = self.inverse();
+        let data: BoardData<T> = (0..self.size)
+            .map(|row| {
+                (0..self.size)
+                    .map(|col| {
+                        let (from_row, from_col) = inverse.map_cell(row, col);
+                        board.at(from_row, from_col).unwrap().clone()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Board::from(&data)
+    }
+
+    pub fn apply(&self, board: &Board) -> StrResult<Board> {
+        if let Some(cell) = board.find(|&v| v > self.size) {
+            return Err(format!(
+                "Value of {} in ({},{}) is not valid: Too big",
+                cell.value, cell.row, cell.col
+            ));
+        }
+
+        let moved = self.apply_positions(board)?;
+        let data: BoardData = moved
+            .get_rows()
+            .iter()
+            .map(|row| row.iter().map(|&v| self.map_digit(v)).collect())
+            .collect();
+
+        Board::from(&data)
+    }
+}
@@ -17,3 +17,21 @@ where
         write!(f, "({},{}) -> {}", self.row, self.col, self.value)
     }
 }
+
+impl<T> PositionalValue<T> {
+    pub fn new(row: usize, col: usize, value: T) -> Self {
+        PositionalValue { row, col, value }
+    }
+
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    pub fn col(&self) -> usize {
+        self.col
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
@@ -1,8 +1,16 @@
 extern crate core;
 
 mod utils;
-use sudoku_core::board::{Board, BoardData};
-use sudoku_core::solve::simple_solve;
+use sudoku_core::analyze::board_report;
+use sudoku_core::board::{Board, BoardData, Patch};
+use sudoku_core::contradiction::{find_contradiction, ContradictionKind, Origin};
+use sudoku_core::diagnose::{
+    find_ambiguity, find_mistakes, minimal_unsatisfiable_givens, suggest_givens,
+};
+use sudoku_core::generate::{daily_puzzle, generate, generate_from_mask, GenerateOptions};
+use sudoku_core::solve::{find_backbone, find_backdoors, simple_solve};
+use sudoku_core::steps::{Difficulty, House, Technique};
+use sudoku_core::types::PositionalValue;
 use sudoku_core::validators::is_valid_sudoku;
 use wasm_bindgen::prelude::*;
 
@@ -31,8 +39,7 @@ pub fn greet() {
     alert("Hello, wasm-lib!");
 }
 
-#[wasm_bindgen]
-pub fn solve(arr: &[usize]) -> Result<Vec<usize>, String> {
+fn board_from_flat(arr: &[usize]) -> Result<Board, String> {
     let flat_data = Vec::from(arr);
     let square_size = (flat_data.len() as f32).sqrt().floor() as usize;
     let data = flat_data
@@ -40,28 +47,306 @@ pub fn solve(arr: &[usize]) -> Result<Vec<usize>, String> {
         .map(|slice| slice.to_vec())
         .collect::<BoardData>();
 
-    let board = Board::from(&data)?;
+    Board::from(&data)
+}
+
+fn to_flat(board: &Board) -> Vec<usize> {
+    board.get_rows_flat().into_iter().copied().collect()
+}
+
+#[wasm_bindgen]
+pub fn solve(arr: &[usize]) -> Result<Vec<usize>, String> {
+    let board = board_from_flat(arr)?;
     let solved = simple_solve(&board)?;
 
-    let solved_flat = solved
-        .get_rows_flat()
+    return Ok(to_flat(&solved));
+}
+
+#[wasm_bindgen]
+pub fn is_valid(arr: &[usize]) -> bool {
+    let board = board_from_flat(arr);
+
+    return board.is_ok() && is_valid_sudoku(&board.unwrap());
+}
+
+// why the board can't be solved, empty when no contradiction is found. flattened as:
+// kind (0 - empty cell, 1 - digit missing from a house, 2 - digit twice in a house),
+// the cell's index or the house (0 - row, 1 - col, 2 - square) and its index, the digit (0 for a cell).
+// then sections, each starting with its number of entries:
+// - removals: a cell's index, a removed digit and the index of the cell that removed it.
+// - placements the removals depend on, in the order they were made: the cell's index, its value,
+//   the technique (0 - given, 1 - naked single, 2 - hidden single), its house (3 when none)
+//   and the house's index, then the number of cells that caused it followed by their indices.
+// - givens the contradiction traces back to: a cell's index and its value.
+#[wasm_bindgen]
+pub fn explain_contradiction(arr: &[usize]) -> Result<Vec<usize>, String> {
+    let board = board_from_flat(arr)?;
+    let size = board.get_size();
+    let index = |(row, col): (usize, usize)| row * size + col;
+    let house_parts = |house: &House| match *house {
+        House::Row(i) => [0, i],
+        House::Col(i) => [1, i],
+        House::Square(i) => [2, i],
+    };
+
+    let contradiction = match find_contradiction(&board)? {
+        Some(contradiction) => contradiction,
+        None => return Ok(vec![]),
+    };
+
+    let mut flat = match &contradiction.kind {
+        ContradictionKind::EmptyCell { row, col } => vec![0, index((*row, *col)), 0, 0],
+        ContradictionKind::MissingDigit { house, digit } => {
+            [vec![1], house_parts(house).to_vec(), vec![*digit]].concat()
+        }
+        ContradictionKind::DuplicateDigit { house, digit } => {
+            [vec![2], house_parts(house).to_vec(), vec![*digit]].concat()
+        }
+    };
+
+    flat.push(contradiction.removals.len());
+    for removal in contradiction.removals.iter() {
+        flat.extend([index(removal.cell), removal.digit, index(removal.by)]);
+    }
+
+    flat.push(contradiction.placements.len());
+    for placement in contradiction.placements.iter() {
+        let position = &placement.position;
+        flat.extend([index((position.row(), position.col())), *position.value()]);
+
+        match &placement.origin {
+            Origin::Given => flat.extend([0, 3, 0, 0]),
+            Origin::Deduced {
+                technique,
+                house,
+                causes,
+            } => {
+                let technique = match technique {
+                    Technique::NakedSingle => 1,
+                    Technique::HiddenSingle => 2,
+                    _ => return Err(format!("Unexpected {} placement", technique.name())),
+                };
+                flat.push(technique);
+                flat.extend(house.as_ref().map_or([3, 0], house_parts));
+                flat.push(causes.len());
+                flat.extend(causes.iter().map(|&cell| index(cell)));
+            }
+        }
+    }
+
+    flat.push(contradiction.givens.len());
+    for given in contradiction.givens.iter() {
+        flat.extend([index((given.row(), given.col())), *given.value()]);
+    }
+
+    Ok(flat)
+}
+
+// flat indices of a minimal set of givens that can't be solved, empty when the board has a solution.
+#[wasm_bindgen]
+pub fn unsatisfiable_givens(arr: &[usize]) -> Result<Vec<usize>, String> {
+    let board = board_from_flat(arr)?;
+    let size = board.get_size();
+    let givens = minimal_unsatisfiable_givens(&board)?.unwrap_or_default();
+
+    Ok(givens.iter().map(|g| g.row() * size + g.col()).collect())
+}
+
+// marks each cell of the current board: 0 - fine, 1 - wrong, 2 - can't be judged (several solutions).
+#[wasm_bindgen]
+pub fn check_entries(givens: &[usize], current: &[usize]) -> Result<Vec<usize>, String> {
+    let givens = board_from_flat(givens)?;
+    let current = board_from_flat(current)?;
+    let size = givens.get_size();
+    let report = find_mistakes(&givens, &current)?;
+    let mut marks = vec![0; size * size];
+
+    for entry in report.wrong.iter() {
+        marks[entry.row() * size + entry.col()] = 1;
+    }
+
+    for entry in report.undecidable.iter() {
+        marks[entry.row() * size + entry.col()] = 2;
+    }
+
+    Ok(marks)
+}
+
+// a new puzzle with a unique solution: the puzzle's cells followed by the solution's cells.
+#[wasm_bindgen]
+pub fn generate_puzzle(size: usize) -> Result<Vec<usize>, String> {
+    let generated = generate(size, &GenerateOptions::default())?;
+    let mut flat = to_flat(&generated.puzzle);
+    flat.extend(to_flat(&generated.solution));
+
+    Ok(flat)
+}
+
+// a puzzle whose givens are the non zero cells of the mask, flattened like `generate_puzzle`.
+#[wasm_bindgen]
+pub fn generate_puzzle_from_mask(mask: &[usize]) -> Result<Vec<usize>, String> {
+    let mask_board = board_from_flat(mask)?;
+    let data: BoardData<bool> = mask_board
+        .get_rows()
         .iter()
-        .map(|&x| *x)
-        .collect::<Vec<_>>();
+        .map(|row| row.iter().map(|&cell| cell != 0).collect())
+        .collect();
 
-    return Ok(solved_flat);
+    let generated = generate_from_mask(&Board::from(&data)?, &GenerateOptions::default())?;
+    let mut flat = to_flat(&generated.puzzle);
+    flat.extend(to_flat(&generated.solution));
+
+    Ok(flat)
 }
 
+// givens to add so the board has a single solution, the suggested values on an otherwise empty board.
 #[wasm_bindgen]
-pub fn is_valid(arr: &[usize]) -> bool {
-    let flat_data = Vec::from(arr);
-    let square_size = (flat_data.len() as f32).sqrt().floor() as usize;
-    let data = flat_data
-        .chunks(square_size)
-        .map(|slice| slice.to_vec())
-        .collect::<BoardData>();
+pub fn suggest_missing_givens(arr: &[usize]) -> Result<Vec<usize>, String> {
+    let board = board_from_flat(arr)?;
+    let size = board.get_size();
+    let mut suggested = vec![0; size * size];
 
-    let board = Board::from(&data);
+    for given in suggest_givens(&board)? {
+        suggested[given.row() * size + given.col()] = *given.value();
+    }
 
-    return board.is_ok() && is_valid_sudoku(&board.unwrap());
+    Ok(suggested)
+}
+
+// the puzzle of the day for `key`, flattened like `generate_puzzle`.
+// difficulty goes from 0 (easy) to 4 (extreme). expert (3) can take many seconds,
+// so call it from a web worker.
+#[wasm_bindgen]
+pub fn daily(algorithm: &str, key: &str, difficulty: u8) -> Result<Vec<usize>, String> {
+    let difficulty = match difficulty {
+        0 => Difficulty::Easy,
+        1 => Difficulty::Medium,
+        2 => Difficulty::Hard,
+        3 => Difficulty::Expert,
+        4 => Difficulty::Extreme,
+        _ => return Err(format!("Unknown difficulty: {difficulty}")),
+    };
+
+    let generated = daily_puzzle(algorithm, key, difficulty)?;
+    let mut flat = to_flat(&generated.puzzle);
+    flat.extend(to_flat(&generated.solution));
+
+    Ok(flat)
+}
+
+// the changed cells as flat triples of index, old value and new value.
+#[wasm_bindgen]
+pub fn diff_boards(before: &[usize], after: &[usize]) -> Result<Vec<usize>, String> {
+    let before = board_from_flat(before)?;
+    let patch = before.diff(&board_from_flat(after)?)?;
+    let size = patch.get_size();
+
+    Ok(patch
+        .changes()
+        .iter()
+        .flat_map(|(old, new)| [old.row() * size + old.col(), *old.value(), *new.value()])
+        .collect())
+}
+
+// applies triples made by `diff_boards`, or undoes them when `revert` is set.
+#[wasm_bindgen]
+pub fn patch_board(arr: &[usize], patch: &[usize], revert: bool) -> Result<Vec<usize>, String> {
+    let mut board = board_from_flat(arr)?;
+    let size = board.get_size();
+
+    if !patch.len().is_multiple_of(3) {
+        return Err("Patch must be made of index, old value and new value triples".to_string());
+    }
+
+    let changes = patch
+        .chunks(3)
+        .map(|change| {
+            (
+                PositionalValue::new(change[0] / size, change[0] % size, change[1]),
+                PositionalValue::new(change[0] / size, change[0] % size, change[2]),
+            )
+        })
+        .collect();
+    let patch = Patch::from_changes(size, changes)?;
+
+    match revert {
+        true => board.revert_patch(&patch)?,
+        false => board.apply_patch(&patch)?,
+    }
+
+    Ok(to_flat(&board))
+}
+
+// the board's metrics flattened as: clues, placed by propagation, candidates, bivalue cells,
+// then size values each of givens per row, per col, per square, digit frequency
+// and 1 or 0 for every fully placed digit.
+#[wasm_bindgen]
+pub fn board_metrics(arr: &[usize]) -> Result<Vec<usize>, String> {
+    let board = board_from_flat(arr)?;
+    let report = board_report(&board)?;
+    let placed: Vec<usize> = (1..=board.get_size())
+        .map(|digit| report.placed_digits.contains(&digit) as usize)
+        .collect();
+
+    let mut flat = vec![
+        report.clues,
+        report.placed_by_propagation,
+        report.candidates,
+        report.bivalue_cells,
+    ];
+    flat.extend(report.givens_per_row);
+    flat.extend(report.givens_per_col);
+    flat.extend(report.givens_per_square);
+    flat.extend(report.digit_frequency);
+    flat.extend(placed);
+
+    Ok(flat)
+}
+
+// the fewest hints after which singles solve the board, on an otherwise empty board.
+// empty when singles already solve it, or when it needs more than two hints.
+#[wasm_bindgen]
+pub fn big_hint(arr: &[usize]) -> Result<Vec<usize>, String> {
+    let board = board_from_flat(arr)?;
+    let size = board.get_size();
+    let mut hints = vec![0; size * size];
+
+    if let Some(backdoors) = find_backdoors(&board, 2)? {
+        for hint in backdoors.sets[0].iter() {
+            hints[hint.row() * size + hint.col()] = *hint.value();
+        }
+    }
+
+    Ok(hints)
+}
+
+// the board with every cell that is the same in all its solutions filled in,
+// the cells the solutions disagree on are left 0.
+#[wasm_bindgen]
+pub fn certain_cells(arr: &[usize]) -> Result<Vec<usize>, String> {
+    let mut board = board_from_flat(arr)?;
+
+    for fixed in find_backbone(&board)?.fixed {
+        board.set(fixed.row(), fixed.col(), *fixed.value())?;
+    }
+
+    Ok(to_flat(&board))
+}
+
+// two solutions differing in as few cells as possible, the first one's cells followed by
+// the second one's, then 1 when no two solutions differ in fewer cells or 0 when the search gave up.
+// empty when the board has a single solution.
+#[wasm_bindgen]
+pub fn ambiguous_solutions(arr: &[usize]) -> Result<Vec<usize>, String> {
+    let board = board_from_flat(arr)?;
+
+    Ok(match find_ambiguity(&board)? {
+        Some(ambiguity) => {
+            let mut flat = to_flat(&ambiguity.first);
+            flat.extend(to_flat(&ambiguity.second));
+            flat.push(ambiguity.minimal as usize);
+            flat
+        }
+        None => vec![],
+    })
 }
@@ -0,0 +1,107 @@
+use wasm_lib::explain_contradiction;
+
+struct DecodedPlacement {
+    cell: usize,
+    value: usize,
+    technique: usize,
+    house: [usize; 2],
+    causes: Vec<usize>,
+}
+
+struct Decoded {
+    header: Vec<usize>,
+    removals: Vec<[usize; 3]>,
+    placements: Vec<DecodedPlacement>,
+    givens: Vec<[usize; 2]>,
+}
+
+// reads the sections the way a client would, failing on any leftover value
+fn decode(flat: &[usize]) -> Decoded {
+    let mut values = flat.iter().copied();
+    let mut next = || values.next().expect("Encoding ended early");
+
+    let header = (0..4).map(|_| next()).collect();
+    let removals = (0..next()).map(|_| [next(), next(), next()]).collect();
+    let placements = (0..next())
+        .map(|_| {
+            let (cell, value, technique) = (next(), next(), next());
+            let house = [next(), next()];
+            let causes = (0..next()).map(|_| next()).collect();
+
+            DecodedPlacement {
+                cell,
+                value,
+                technique,
+                house,
+                causes,
+            }
+        })
+        .collect();
+    let givens = (0..next()).map(|_| [next(), next()]).collect();
+
+    assert_eq!(values.next(), None);
+
+    Decoded {
+        header,
+        removals,
+        placements,
+        givens,
+    }
+}
+
+#[test]
+fn contradiction_traces_back_to_givens() -> Result<(), String> {
+    // (0,3) can only be 4, which leaves nothing for (1,2)
+    #[rustfmt::skip]
+    let board = [
+        1, 2, 3, 0,
+        0, 0, 0, 0,
+        0, 0, 1, 0,
+        0, 0, 2, 0,
+    ];
+
+    let decoded = decode(&explain_contradiction(&board)?);
+
+    assert_eq!(decoded.header, vec![0, 6, 0, 0]);
+    assert!(!decoded.removals.is_empty());
+    assert!(decoded.removals.iter().all(|removal| removal[0] == 6));
+
+    let placed: Vec<usize> = decoded.placements.iter().map(|p| p.cell).collect();
+    assert!(decoded
+        .removals
+        .iter()
+        .all(|removal| placed.contains(&removal[2])));
+
+    let deduced = decoded
+        .placements
+        .iter()
+        .position(|p| p.technique != 0)
+        .expect("Deduced placement not found");
+    let placement = &decoded.placements[deduced];
+    assert_eq!((placement.cell, placement.value), (3, 4));
+    assert_eq!((placement.technique, placement.house), (1, [3, 0]));
+    assert!(!placement.causes.is_empty());
+    assert!(placement
+        .causes
+        .iter()
+        .all(|cause| placed[..deduced].contains(cause)));
+
+    for given in decoded.givens.iter() {
+        assert_eq!(board[given[0]], given[1]);
+    }
+    assert_eq!(
+        decoded.givens.len(),
+        decoded
+            .placements
+            .iter()
+            .filter(|p| p.technique == 0)
+            .count()
+    );
+    Ok(())
+}
+
+#[test]
+fn solvable_board_has_no_contradiction() -> Result<(), String> {
+    assert!(explain_contradiction(&[0; 16])?.is_empty());
+    Ok(())
+}